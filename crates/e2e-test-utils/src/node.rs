@@ -9,6 +9,7 @@ use futures_util::Future;
 use jsonrpsee::{
     core::middleware::layer::RpcLogger,
     http_client::{transport::HttpBackend, HttpClient, RpcService},
+    ws_client::WsClient,
 };
 use reth_chainspec::EthereumHardforks;
 use reth_network_api::test_utils::PeersHandleProvider;
@@ -304,6 +305,11 @@ where
         self.inner.rpc_server_handle().http_client()
     }
 
+    /// Returns a websocket RPC client, if the websocket server is enabled.
+    pub async fn ws_client(&self) -> Option<WsClient> {
+        self.inner.rpc_server_handle().ws_client().await
+    }
+
     /// Returns an Engine API client.
     pub fn engine_api_client(
         &self,
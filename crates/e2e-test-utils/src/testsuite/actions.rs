@@ -1,19 +1,68 @@
 //! Actions that can be performed in tests.
 
-use crate::testsuite::Environment;
-use alloy_primitives::{Bytes, B256, U256};
+use crate::testsuite::{
+    setup::{connect_nodes, disconnect_nodes, node_record},
+    BlockMetric, CollectedBlockRange, Environment, InvalidPayload, LatestBlockInfo,
+    MetricsSnapshot, StateGrowth,
+};
+use alloy_consensus::{
+    proofs::calculate_receipt_root, Transaction as _, TxEnvelope, EMPTY_OMMER_ROOT_HASH,
+    EMPTY_ROOT_HASH,
+};
+use alloy_eips::{
+    eip1559::BaseFeeParams,
+    eip4844::{kzg_to_versioned_hash, BlobTransactionSidecar},
+    eip4895::Withdrawal,
+    eip7685::{Requests, RequestsOrHash},
+    eip7840::BlobParams,
+    BlockId, BlockNumberOrTag, Decodable2718, Typed2718,
+};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U128, U256};
 use alloy_rpc_types_engine::{
-    payload::ExecutionPayloadEnvelopeV3, ExecutionPayloadV3, ForkchoiceState, PayloadAttributes,
-    PayloadStatusEnum,
+    payload::{
+        ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
+        ExecutionPayloadFieldV2,
+    },
+    BlobsBundleV1, ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState, PayloadAttributes,
+    PayloadId, PayloadStatusEnum,
+};
+use alloy_rpc_types_eth::{
+    Block, Filter, Header, Receipt, SyncStatus, Transaction, TransactionReceipt,
 };
-use alloy_rpc_types_eth::{Block, Header, Receipt, Transaction};
+use alloy_serde::JsonStorageKey;
 use eyre::Result;
-use futures_util::future::BoxFuture;
+use futures_util::{
+    future::{join_all, try_join_all, BoxFuture},
+    StreamExt,
+};
+use jsonrpsee::{
+    core::{
+        client::{Subscription, SubscriptionClientT},
+        rpc_params,
+    },
+    http_client::HttpClient,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use reth_network_api::ReputationChangeKind;
+use reth_network_peers::NodeRecord;
 use reth_node_api::{EngineTypes, PayloadTypes};
-use reth_rpc_api::clients::{EngineApiClient, EthApiClient};
-use std::{future::Future, marker::PhantomData, time::Duration};
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadAttributes as _};
+use reth_primitives_traits::{ExtendedTxEnvelope, SignerRecoverable};
+use reth_rpc_api::clients::{
+    AdminApiClient, EngineApiClient, EthApiClient, EthFilterApiClient, MinerApiClient,
+    TxPoolApiClient,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    marker::PhantomData,
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
 use tokio::time::sleep;
-use tracing::debug;
+use tracing::{debug, info};
 
 /// An action that can be performed on an instance.
 ///
@@ -41,6 +90,12 @@ impl<I: 'static> ActionBox<I> {
     }
 }
 
+impl<I> From<Box<dyn Action<I>>> for ActionBox<I> {
+    fn from(action: Box<dyn Action<I>>) -> Self {
+        Self(action)
+    }
+}
+
 /// Implementation of `Action` for any function/closure that takes an Environment
 /// reference and returns a Future resolving to Result<()>.
 ///
@@ -71,6 +126,8 @@ where
     /// Block's payload attributes
     // TODO: refactor once we have actions to generate payload attributes.
     pub payload_attributes: Engine::PayloadAttributes,
+    /// Sidecars of the blob transactions expected in the block, set with `with_blob_sidecars`
+    blob_sidecars: Vec<BlobTransactionSidecar>,
     /// Tracks engine type
     _phantom: PhantomData<Engine>,
 }
@@ -91,11 +148,26 @@ where
             transactions,
             expected_hash,
             payload_attributes,
+            blob_sidecars: Vec::new(),
             _phantom: Default::default(),
         }
     }
 }
 
+impl<Engine> AssertMineBlock<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>,
+{
+    /// Set the sidecars of the blob transactions expected in the block.
+    ///
+    /// With sidecars the block is built through the V3 engine API, and the built payload is
+    /// submitted back to the node with the versioned hashes of the sidecars, which it must accept.
+    pub fn with_blob_sidecars(mut self, blob_sidecars: Vec<BlobTransactionSidecar>) -> Self {
+        self.blob_sidecars = blob_sidecars;
+        self
+    }
+}
+
 impl<Engine> Action<Engine> for AssertMineBlock<Engine>
 where
     Engine: EngineTypes,
@@ -106,9 +178,7 @@ where
                 return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
             }
 
-            let node_client = &env.node_clients[self.node_idx];
-            let rpc_client = &node_client.rpc;
-            let engine_client = &node_client.engine;
+            let rpc_client = &env.node_clients[self.node_idx].rpc;
 
             // get the latest block to use as parent
             let latest_block =
@@ -131,8 +201,12 @@ where
                 finalized_block_hash: parent_hash,
             };
 
+            if !self.blob_sidecars.is_empty() {
+                return self.mine_block_with_blobs(env, fork_choice_state).await
+            }
+
             let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v2(
-                engine_client,
+                env.engine_client(self.node_idx).await?,
                 fork_choice_state,
                 Some(self.payload_attributes.clone()),
             )
@@ -147,9 +221,11 @@ where
                         debug!("Got payload ID: {payload_id}");
 
                         // get the payload that was built
-                        let _engine_payload =
-                            EngineApiClient::<Engine>::get_payload_v2(engine_client, payload_id)
-                                .await?;
+                        let _engine_payload = EngineApiClient::<Engine>::get_payload_v2(
+                            env.engine_client(self.node_idx).await?,
+                            payload_id,
+                        )
+                        .await?;
                         Ok(())
                     } else {
                         Err(eyre::eyre!("No payload ID returned from forkchoiceUpdated"))
@@ -160,6 +236,112 @@ where
         })
     }
 }
+
+impl<Engine> AssertMineBlock<Engine>
+where
+    Engine: EngineTypes,
+{
+    /// Builds the block through the V3 engine API and submits it back to the node with the
+    /// versioned hashes of the blob sidecars.
+    async fn mine_block_with_blobs(
+        &self,
+        env: &Environment<Engine>,
+        fork_choice_state: ForkchoiceState,
+    ) -> Result<()> {
+        let parent_beacon_block_root =
+            self.payload_attributes.parent_beacon_block_root().ok_or_else(|| {
+                eyre::eyre!(
+                    "Payload attributes of a block with blobs need a parent beacon block root"
+                )
+            })?;
+
+        let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+            env.engine_client(self.node_idx).await?,
+            fork_choice_state,
+            Some(self.payload_attributes.clone()),
+        )
+        .await?;
+        debug!("FCU result: {:?}", fcu_result);
+        if !fcu_result.payload_status.status.is_valid() {
+            return Err(eyre::eyre!("Payload status not valid: {:?}", fcu_result.payload_status))
+        }
+        let payload_id = fcu_result
+            .payload_id
+            .ok_or_else(|| eyre::eyre!("No payload ID returned from forkchoiceUpdated"))?;
+
+        // blob sidecars can only be set for engines with the Ethereum V3 payload envelope
+        let payload = EngineApiClient::<reth_node_ethereum::EthEngineTypes>::get_payload_v3(
+            env.engine_client(self.node_idx).await?,
+            payload_id,
+        )
+        .await?
+        .execution_payload;
+
+        let mut referenced = Vec::new();
+        for raw in &payload.payload_inner.payload_inner.transactions {
+            let tx = TxEnvelope::decode_2718(&mut raw.as_ref())?;
+            if let Some(hashes) = tx.blob_versioned_hashes() {
+                referenced.extend_from_slice(hashes);
+            }
+        }
+        let versioned_hashes = sidecar_versioned_hashes(&self.blob_sidecars, &referenced)?;
+
+        let status = EngineApiClient::<Engine>::new_payload_v3(
+            env.engine_client(self.node_idx).await?,
+            payload,
+            versioned_hashes.clone(),
+            parent_beacon_block_root,
+        )
+        .await?;
+        if status.status != PayloadStatusEnum::Valid {
+            return Err(eyre::eyre!(
+                "Payload {payload_id} with {} blobs not accepted: {:?}",
+                versioned_hashes.len(),
+                status.status
+            ))
+        }
+
+        debug!("Payload {payload_id} with {} blobs accepted", versioned_hashes.len());
+        Ok(())
+    }
+}
+
+/// Computes the versioned hashes of the blobs carried by the sidecars and checks they match the
+/// versioned hashes referenced by the block's transactions, in order.
+fn sidecar_versioned_hashes(
+    sidecars: &[BlobTransactionSidecar],
+    referenced: &[B256],
+) -> Result<Vec<B256>> {
+    for (idx, sidecar) in sidecars.iter().enumerate() {
+        if sidecar.blobs.len() != sidecar.commitments.len() {
+            return Err(eyre::eyre!(
+                "Sidecar {idx} has {} blobs but {} commitments",
+                sidecar.blobs.len(),
+                sidecar.commitments.len()
+            ))
+        }
+    }
+
+    let versioned_hashes =
+        sidecars.iter().flat_map(BlobTransactionSidecar::versioned_hashes).collect::<Vec<_>>();
+    if versioned_hashes.len() != referenced.len() {
+        return Err(eyre::eyre!(
+            "Sidecars carry {} blobs, block transactions reference {} versioned hashes",
+            versioned_hashes.len(),
+            referenced.len()
+        ))
+    }
+    if let Some(idx) = versioned_hashes.iter().zip(referenced).position(|(a, b)| a != b) {
+        return Err(eyre::eyre!(
+            "Versioned hash {idx} of the sidecars is {}, block transactions reference {}",
+            versioned_hashes[idx],
+            referenced[idx]
+        ))
+    }
+
+    Ok(versioned_hashes)
+}
+
 /// Pick the next block producer based on the latest block information.
 #[derive(Debug, Default)]
 pub struct PickNextBlockProducer {}
@@ -222,8 +404,56 @@ where
 }
 
 /// Store payload attributes for the next block.
+///
+/// Fields that are not set fall back to a random fee recipient and `prev_randao`, no withdrawals
+/// and a zero parent beacon block root. Tests that need reproducible blocks can pin them.
 #[derive(Debug, Default)]
-pub struct GeneratePayloadAttributes {}
+pub struct GeneratePayloadAttributes {
+    /// Fee recipient of the next block
+    pub fee_recipient: Option<Address>,
+    /// `prev_randao` of the next block
+    pub prev_randao: Option<B256>,
+    /// Withdrawals of the next block
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Parent beacon block root of the next block
+    pub parent_beacon_block_root: Option<B256>,
+}
+
+impl GeneratePayloadAttributes {
+    /// Create a new `GeneratePayloadAttributes` action with no pinned fields
+    pub const fn new() -> Self {
+        Self {
+            fee_recipient: None,
+            prev_randao: None,
+            withdrawals: None,
+            parent_beacon_block_root: None,
+        }
+    }
+
+    /// Set the fee recipient of the next block
+    pub const fn with_fee_recipient(mut self, fee_recipient: Address) -> Self {
+        self.fee_recipient = Some(fee_recipient);
+        self
+    }
+
+    /// Set the `prev_randao` of the next block
+    pub const fn with_prev_randao(mut self, prev_randao: B256) -> Self {
+        self.prev_randao = Some(prev_randao);
+        self
+    }
+
+    /// Set the withdrawals of the next block
+    pub fn with_withdrawals(mut self, withdrawals: Vec<Withdrawal>) -> Self {
+        self.withdrawals = Some(withdrawals);
+        self
+    }
+
+    /// Set the parent beacon block root of the next block
+    pub const fn with_parent_beacon_block_root(mut self, parent_beacon_block_root: B256) -> Self {
+        self.parent_beacon_block_root = Some(parent_beacon_block_root);
+        self
+    }
+}
 
 impl<Engine> Action<Engine> for GeneratePayloadAttributes
 where
@@ -239,10 +469,10 @@ where
             let timestamp = env.latest_header_time + env.block_timestamp_increment;
             let payload_attributes = alloy_rpc_types_engine::PayloadAttributes {
                 timestamp,
-                prev_randao: B256::random(),
-                suggested_fee_recipient: alloy_primitives::Address::random(),
-                withdrawals: Some(vec![]),
-                parent_beacon_block_root: Some(B256::ZERO),
+                prev_randao: self.prev_randao.unwrap_or_else(B256::random),
+                suggested_fee_recipient: self.fee_recipient.unwrap_or_else(Address::random),
+                withdrawals: Some(self.withdrawals.clone().unwrap_or_default()),
+                parent_beacon_block_root: Some(self.parent_beacon_block_root.unwrap_or_default()),
             };
 
             env.payload_attributes.insert(latest_block.number + 1, payload_attributes);
@@ -252,8 +482,27 @@ where
     }
 }
 /// Action that generates the next payload
-#[derive(Debug, Default)]
-pub struct GenerateNextPayload {}
+///
+/// The payload is fetched from the builder after `build_delay`, one second by default.
+#[derive(Debug)]
+pub struct GenerateNextPayload {
+    /// Time given to the builder to build the payload
+    pub build_delay: DelayAction,
+}
+
+impl GenerateNextPayload {
+    /// Set the time given to the builder to build the payload
+    pub const fn with_build_delay(mut self, build_delay: DelayAction) -> Self {
+        self.build_delay = build_delay;
+        self
+    }
+}
+
+impl Default for GenerateNextPayload {
+    fn default() -> Self {
+        Self { build_delay: DelayAction::new(Duration::from_secs(1)) }
+    }
+}
 
 impl<Engine> Action<Engine> for GenerateNextPayload
 where
@@ -284,7 +533,7 @@ where
                 .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
 
             let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
-                &env.node_clients[0].engine,
+                env.engine_client(0).await?,
                 fork_choice_state,
                 Some(payload_attributes.clone()),
             )
@@ -299,13 +548,14 @@ where
             debug!("Received payload ID: {:?}", payload_id);
             env.next_payload_id = Some(payload_id);
 
-            sleep(Duration::from_secs(1)).await;
+            self.build_delay.wait().await;
 
             let built_payload: PayloadAttributes =
-                EngineApiClient::<Engine>::get_payload_v3(&env.node_clients[0].engine, payload_id)
+                EngineApiClient::<Engine>::get_payload_v3(env.engine_client(0).await?, payload_id)
                     .await?
                     .into();
             env.payload_id_history.insert(latest_block.number + 1, payload_id);
+            env.block_producers.insert(latest_block.number + 1, 0);
             env.latest_payload_built = Some(built_payload);
 
             Ok(())
@@ -315,7 +565,26 @@ where
 
 ///Action that broadcasts the latest fork choice state to all clients
 #[derive(Debug, Default)]
-pub struct BroadcastLatestForkchoice {}
+pub struct BroadcastLatestForkchoice {
+    /// Safe block hash to broadcast instead of the head
+    pub safe: Option<B256>,
+    /// Finalized block hash to broadcast instead of the head
+    pub finalized: Option<B256>,
+}
+
+impl BroadcastLatestForkchoice {
+    /// Set the safe block hash, which must be an ancestor of the head
+    pub const fn with_safe(mut self, safe: B256) -> Self {
+        self.safe = Some(safe);
+        self
+    }
+
+    /// Set the finalized block hash, which must be an ancestor of the head
+    pub const fn with_finalized(mut self, finalized: B256) -> Self {
+        self.finalized = Some(finalized);
+        self
+    }
+}
 
 impl<Engine> Action<Engine> for BroadcastLatestForkchoice
 where
@@ -338,10 +607,25 @@ where
             let parent_hash = latest_block.hash;
             debug!("Latest block hash: {parent_hash}");
 
+            let producer_idx = env.last_producer_idx.unwrap_or(0);
+            let producer_rpc = &env
+                .node_clients
+                .get(producer_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", producer_idx))?
+                .rpc;
+            for (name, hash) in [("Safe", self.safe), ("Finalized", self.finalized)] {
+                let Some(hash) = hash else { continue };
+                if !is_ancestor(producer_rpc, hash, parent_hash).await? {
+                    return Err(eyre::eyre!(
+                        "{name} block {hash} is not an ancestor of the head {parent_hash}"
+                    ));
+                }
+            }
+
             let fork_choice_state = ForkchoiceState {
                 head_block_hash: parent_hash,
-                safe_block_hash: parent_hash,
-                finalized_block_hash: parent_hash,
+                safe_block_hash: self.safe.unwrap_or(parent_hash),
+                finalized_block_hash: self.finalized.unwrap_or(parent_hash),
             };
             debug!(
                 "Broadcasting forkchoice update to {} clients. Head: {:?}",
@@ -349,11 +633,9 @@ where
                 fork_choice_state.head_block_hash
             );
 
-            for (idx, client) in env.node_clients.iter().enumerate() {
-                let engine_client = &client.engine;
-
+            for idx in 0..env.node_clients.len() {
                 match EngineApiClient::<Engine>::fork_choice_updated_v3(
-                    engine_client,
+                    env.engine_client(idx).await?,
                     fork_choice_state,
                     payload.clone(),
                 )
@@ -380,6 +662,27 @@ where
     }
 }
 
+/// Returns whether `ancestor` is the block `head` or one of its ancestors, walking back the parent
+/// hashes from `head`.
+async fn is_ancestor(rpc: &HttpClient, ancestor: B256, head: B256) -> Result<bool> {
+    let ancestor =
+        EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(rpc, ancestor)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Block {ancestor} not found"))?;
+    let mut current =
+        EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(rpc, head)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Block {head} not found"))?;
+    while current.number > ancestor.number {
+        let parent_hash = current.parent_hash;
+        current =
+            EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(rpc, parent_hash)
+                .await?
+                .ok_or_else(|| eyre::eyre!("Block {parent_hash} not found"))?;
+    }
+    Ok(current.hash == ancestor.hash)
+}
+
 /// Action that checks whether the broadcasted new payload has been accepted
 #[derive(Debug, Default)]
 pub struct CheckPayloadAccepted {}
@@ -422,8 +725,11 @@ where
                     .as_ref()
                     .ok_or_else(|| eyre::eyre!("No next built payload found"))?;
 
-                let built_payload =
-                    EngineApiClient::<Engine>::get_payload_v3(&client.engine, payload_id).await?;
+                let built_payload = EngineApiClient::<Engine>::get_payload_v3(
+                    env.engine_client(idx).await?,
+                    payload_id,
+                )
+                .await?;
 
                 let execution_payload_envelope: ExecutionPayloadEnvelopeV3 = built_payload;
                 let new_payload_block_hash = execution_payload_envelope
@@ -553,8 +859,22 @@ impl<I: Sync + Send + 'static> Action<I> for Sequence<I> {
 }
 
 /// Action that broadcasts the next new payload
+///
+/// The versioned hashes sent with the payload are computed from `blob_sidecars`, they must match
+/// the blob versioned hashes referenced by the block's transactions.
 #[derive(Debug, Default)]
-pub struct BroadcastNextNewPayload {}
+pub struct BroadcastNextNewPayload {
+    /// Sidecars of the blob transactions in the payload
+    pub blob_sidecars: Vec<BlobTransactionSidecar>,
+}
+
+impl BroadcastNextNewPayload {
+    /// Set the sidecars of the blob transactions in the payload
+    pub fn with_blob_sidecars(mut self, blob_sidecars: Vec<BlobTransactionSidecar>) -> Self {
+        self.blob_sidecars = blob_sidecars;
+        self
+    }
+}
 
 impl<Engine> Action<Engine> for BroadcastNextNewPayload
 where
@@ -576,8 +896,7 @@ where
             // Loop through all clients and broadcast the next new payload
             let mut successful_broadcast: bool = false;
 
-            for client in &env.node_clients {
-                let engine = &client.engine;
+            for (idx, client) in env.node_clients.iter().enumerate() {
                 let rpc_client = &client.rpc;
 
                 // Get latest block from the client
@@ -585,7 +904,7 @@ where
                     EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
                         rpc_client,
                         alloy_eips::BlockNumberOrTag::Latest,
-                        false,
+                        true,
                     )
                     .await?
                     .ok_or_else(|| eyre::eyre!("No latest block found from rpc"))?;
@@ -631,13 +950,24 @@ where
                     ));
                 }
 
+                let referenced = latest_block
+                    .body
+                    .transactions
+                    .iter()
+                    .filter_map(|tx| tx.blob_versioned_hashes())
+                    .flatten()
+                    .copied()
+                    .collect::<Vec<_>>();
+                let versioned_hashes = sidecar_versioned_hashes(&self.blob_sidecars, &referenced)
+                    .map_err(|e| eyre::eyre!("Client {idx}: {e}"))?;
+
                 // Construct and broadcast the execution payload from the latest block
                 // The latest block should contain the latest_payload_built
                 let execution_payload = ExecutionPayloadV3::from_block_slow(&latest_block);
                 let result = EngineApiClient::<Engine>::new_payload_v3(
-                    engine,
+                    env.engine_client(idx).await?,
                     execution_payload,
-                    vec![],
+                    versioned_hashes,
                     parent_beacon_block_root,
                 )
                 .await?;
@@ -665,3 +995,7206 @@ where
         })
     }
 }
+
+/// Action that asserts a post-merge block has zero difficulty.
+#[derive(Debug)]
+pub struct AssertDifficultyZero {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The block to check
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertDifficultyZero {
+    /// Create a new `AssertDifficultyZero` action for the given block
+    pub const fn new(node_idx: usize, block: BlockNumberOrTag) -> Self {
+        Self { node_idx, block }
+    }
+}
+
+impl Default for AssertDifficultyZero {
+    fn default() -> Self {
+        Self::new(0, BlockNumberOrTag::Latest)
+    }
+}
+
+impl<Engine> Action<Engine> for AssertDifficultyZero
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &node_client.rpc,
+                self.block,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No header found for block {}", self.block))?;
+
+            if header.inner.difficulty != U256::ZERO {
+                return Err(eyre::eyre!(
+                    "Block {} ({}) has non-zero difficulty: {}",
+                    header.inner.number,
+                    header.hash,
+                    header.inner.difficulty
+                ));
+            }
+
+            debug!("Block {} has zero difficulty", header.inner.number);
+            Ok(())
+        })
+    }
+}
+
+/// Action that scrapes a node's Prometheus endpoint and stores the selected metrics in the
+/// [`Environment`] under the given label.
+///
+/// The metrics server of the nodes must be enabled with
+/// [`Setup::with_metrics`](crate::testsuite::setup::Setup::with_metrics).
+#[derive(Debug)]
+pub struct CaptureMetrics {
+    /// The node index to scrape
+    pub node_idx: usize,
+    /// Label under which the snapshot is stored
+    pub label: String,
+    /// Names of the metrics to capture, all metrics are captured if empty
+    pub metrics: Vec<String>,
+}
+
+impl CaptureMetrics {
+    /// Create a new `CaptureMetrics` action
+    pub fn new(node_idx: usize, label: impl Into<String>, metrics: Vec<String>) -> Self {
+        Self { node_idx, label: label.into(), metrics }
+    }
+}
+
+impl<I> Action<I> for CaptureMetrics
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let snapshot = scrape_metrics(env, self.node_idx, &self.metrics).await?;
+            debug!("Captured {} metrics under label {}", snapshot.len(), self.label);
+            env.metrics_snapshots.insert(self.label.clone(), snapshot);
+            Ok(())
+        })
+    }
+}
+
+/// Action that captures the selected metrics before and after running an inner action and
+/// asserts the delta, attributing the cost of the inner action.
+///
+/// Both snapshots are stored in the [`Environment`] as `{label}_before` and `{label}_after`, and
+/// the delta of every series present in both as `{label}_delta`. Each series set with
+/// `with_expected_delta` must be present in both snapshots and change by an amount within its
+/// range. Like [`CaptureMetrics`] this requires the metrics server of the nodes.
+#[expect(missing_debug_implementations)]
+pub struct MeasureMetricsDelta<I> {
+    /// The node index to scrape
+    pub node_idx: usize,
+    /// Label prefix under which the snapshots are stored
+    pub label: String,
+    /// Names of the metrics to capture, all metrics are captured if empty
+    pub metrics: Vec<String>,
+    /// Expected delta of series, keyed by their full series name including labels
+    pub expected_deltas: Vec<(String, RangeInclusive<f64>)>,
+    /// Action to measure
+    pub inner: Box<dyn Action<I>>,
+}
+
+impl<I> MeasureMetricsDelta<I> {
+    /// Create a new `MeasureMetricsDelta` action
+    pub fn new<A: Action<I>>(
+        node_idx: usize,
+        label: impl Into<String>,
+        metrics: Vec<String>,
+        inner: A,
+    ) -> Self {
+        Self {
+            node_idx,
+            label: label.into(),
+            metrics,
+            expected_deltas: Vec::new(),
+            inner: Box::new(inner),
+        }
+    }
+
+    /// Expect the series to change by an amount within `range` during the inner action
+    pub fn with_expected_delta(
+        mut self,
+        series: impl Into<String>,
+        range: RangeInclusive<f64>,
+    ) -> Self {
+        self.expected_deltas.push((series.into(), range));
+        self
+    }
+}
+
+impl<I> Action<I> for MeasureMetricsDelta<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let before = scrape_metrics(env, self.node_idx, &self.metrics).await?;
+            self.inner.execute(env).await?;
+            let after = scrape_metrics(env, self.node_idx, &self.metrics).await?;
+
+            let delta = metrics_delta(&before, &after);
+            let mut mismatches = Vec::new();
+            for (series, range) in &self.expected_deltas {
+                match delta.get(series) {
+                    Some(value) if range.contains(value) => {}
+                    Some(value) => {
+                        mismatches.push(format!("{series} changed by {value}, expected {range:?}"))
+                    }
+                    None => mismatches.push(format!("{series} was not reported")),
+                }
+            }
+            if !mismatches.is_empty() {
+                return Err(eyre::eyre!(
+                    "Unexpected metric deltas during {}: {}",
+                    self.label,
+                    mismatches.join(", ")
+                ));
+            }
+
+            debug!("Measured {} metric deltas during {}", delta.len(), self.label);
+            env.metrics_snapshots.insert(format!("{}_before", self.label), before);
+            env.metrics_snapshots.insert(format!("{}_after", self.label), after);
+            env.metrics_snapshots.insert(format!("{}_delta", self.label), delta);
+            Ok(())
+        })
+    }
+}
+
+/// Returns the difference of every series present in both snapshots.
+pub fn metrics_delta(before: &MetricsSnapshot, after: &MetricsSnapshot) -> MetricsSnapshot {
+    after
+        .iter()
+        .filter_map(|(name, value)| before.get(name).map(|prev| (name.clone(), value - prev)))
+        .collect()
+}
+
+/// Scrapes the Prometheus endpoint of the node and parses the samples of the given metrics.
+async fn scrape_metrics<I>(
+    env: &Environment<I>,
+    node_idx: usize,
+    metrics: &[String],
+) -> Result<MetricsSnapshot> {
+    let addr = env
+        .node_clients
+        .get(node_idx)
+        .ok_or_else(|| eyre::eyre!("Node index out of bounds: {node_idx}"))?
+        .metrics
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "Node {node_idx} has no metrics server, enable it with `Setup::with_metrics`"
+            )
+        })?;
+    let body =
+        reqwest::get(format!("http://{addr}/metrics")).await?.error_for_status()?.text().await?;
+    Ok(parse_prometheus_text(&body, metrics))
+}
+
+/// Parses samples in the Prometheus text exposition format.
+///
+/// Samples are keyed by their full series name including labels, comments and samples of
+/// metrics not in `metrics` are skipped.
+fn parse_prometheus_text(body: &str, metrics: &[String]) -> MetricsSnapshot {
+    let mut snapshot = MetricsSnapshot::default();
+    for line in body.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // the series may contain spaces inside label values, so split at the closing brace
+        let (series, rest) = match line.rfind('}') {
+            Some(end) => line.split_at(end + 1),
+            None => match line.split_once(char::is_whitespace) {
+                Some(parts) => parts,
+                None => continue,
+            },
+        };
+        let name = series.split('{').next().unwrap_or(series);
+        if !metrics.is_empty() && !metrics.iter().any(|metric| metric == name) {
+            continue;
+        }
+
+        if let Some(Ok(value)) = rest.split_whitespace().next().map(str::parse::<f64>) {
+            snapshot.insert(series.to_string(), value);
+        }
+    }
+    snapshot
+}
+
+/// Action that recomputes the receipts root of a block from its receipts and asserts it matches
+/// the header's `receipts_root`.
+#[derive(Debug)]
+pub struct AssertReceiptRoot {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The block to check
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertReceiptRoot {
+    /// Create a new `AssertReceiptRoot` action for the given block
+    pub const fn new(node_idx: usize, block: BlockNumberOrTag) -> Self {
+        Self { node_idx, block }
+    }
+}
+
+impl Default for AssertReceiptRoot {
+    fn default() -> Self {
+        Self::new(0, BlockNumberOrTag::Latest)
+    }
+}
+
+impl<Engine> Action<Engine> for AssertReceiptRoot
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &node_client.rpc,
+                self.block,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No header found for block {}", self.block))?;
+
+            // fetch the receipts by hash so they belong to the same block as the header
+            let receipts =
+                EthApiClient::<Transaction, Block, TransactionReceipt, Header>::block_receipts(
+                    &node_client.rpc,
+                    BlockId::from(header.hash),
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("No receipts found for block {}", header.hash))?;
+
+            let receipts = receipts
+                .into_iter()
+                .map(|receipt| receipt.into_primitives_receipt().inner)
+                .collect::<Vec<_>>();
+            let receipts_root = calculate_receipt_root(&receipts);
+
+            if receipts_root != header.inner.receipts_root {
+                return Err(eyre::eyre!(
+                    "Receipts root mismatch for block {}: header {}, computed {}",
+                    header.inner.number,
+                    header.inner.receipts_root,
+                    receipts_root
+                ));
+            }
+
+            debug!("Receipts root of block {} matches: {receipts_root}", header.inner.number);
+            Ok(())
+        })
+    }
+}
+
+/// Action that launches a new node and connects it to all running nodes.
+///
+/// The new node is appended to `env.node_clients`. It starts from genesis and catches up with the
+/// cluster through regular sync.
+#[derive(Debug)]
+pub struct AddNode {
+    /// Maximum time to wait for each peer connection to be established
+    pub connect_timeout: Duration,
+}
+
+impl AddNode {
+    /// Create a new `AddNode` action
+    pub const fn new(connect_timeout: Duration) -> Self {
+        Self { connect_timeout }
+    }
+}
+
+impl Default for AddNode {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+impl<I> Action<I> for AddNode
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let launcher = env
+                .node_launcher
+                .clone()
+                .ok_or_else(|| eyre::eyre!("No node launcher available, was the setup applied?"))?;
+
+            let idx = env.node_clients.len();
+            let client = launcher.launch(idx, None).await?;
+
+            for (peer_idx, peer) in env.node_clients.iter().enumerate() {
+                connect_nodes(&client, peer, self.connect_timeout).await.map_err(|e| {
+                    eyre::eyre!("Failed to connect new node {idx} to node {peer_idx}: {e}")
+                })?;
+            }
+
+            debug!("Added node {idx} to the cluster");
+            env.node_clients.push(client);
+            Ok(())
+        })
+    }
+}
+
+/// Action that gracefully shuts down a node and removes it from `env.node_clients`.
+///
+/// Indices of the nodes after the removed one shift down by one, see
+/// [`Environment::remove_node`].
+#[derive(Debug)]
+pub struct RemoveNode {
+    /// The node index to remove
+    pub node_idx: usize,
+    /// Maximum time to wait for the node's tasks to shut down
+    pub shutdown_timeout: Duration,
+}
+
+impl RemoveNode {
+    /// Create a new `RemoveNode` action
+    pub const fn new(node_idx: usize) -> Self {
+        Self { node_idx, shutdown_timeout: Duration::from_secs(10) }
+    }
+
+    /// Set the maximum time to wait for the node to shut down
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+}
+
+impl<I> Action<I> for RemoveNode
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut client = env.remove_node(self.node_idx)?;
+
+            if let Some(lifecycle) = client.lifecycle.as_mut() {
+                if !lifecycle.shutdown(self.shutdown_timeout).await? {
+                    debug!(
+                        "Node {} did not shut down within {:?}",
+                        self.node_idx, self.shutdown_timeout
+                    );
+                }
+            }
+
+            debug!("Removed node {} from the cluster", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that makes a set of nodes build a payload on top of the same parent concurrently.
+///
+/// Each node gets its own fee recipient so the resulting payloads compete with each other. The
+/// built payloads are stored in `env.competing_payloads` keyed by node index, for a subsequent
+/// fork choice resolution step.
+#[derive(Debug)]
+pub struct ProduceBlocksConcurrentlyOnNodes<Engine> {
+    /// Indices of the nodes that build a payload
+    pub node_indices: Vec<usize>,
+    /// Time given to the nodes to build their payloads
+    pub build_time: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ProduceBlocksConcurrentlyOnNodes<Engine> {
+    /// Create a new `ProduceBlocksConcurrentlyOnNodes` action
+    pub fn new(node_indices: Vec<usize>) -> Self {
+        Self { node_indices, build_time: Duration::from_secs(1), _phantom: Default::default() }
+    }
+
+    /// Set the time given to the nodes to build their payloads
+    pub const fn with_build_time(mut self, build_time: Duration) -> Self {
+        self.build_time = build_time;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for ProduceBlocksConcurrentlyOnNodes<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_indices.is_empty() {
+                return Err(eyre::eyre!("No nodes selected to build payloads"));
+            }
+            if let Some(idx) = self.node_indices.iter().find(|idx| **idx >= env.node_clients.len())
+            {
+                return Err(eyre::eyre!("Node index out of bounds: {idx}"));
+            }
+
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let parent_hash = latest_block.hash;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: parent_hash,
+                safe_block_hash: parent_hash,
+                finalized_block_hash: parent_hash,
+            };
+            let timestamp = env.latest_header_time + env.block_timestamp_increment;
+
+            let build_time = self.build_time;
+            let env_ref = &*env;
+            let builds = self.node_indices.iter().map(|&idx| {
+                let payload_attributes = PayloadAttributes {
+                    timestamp,
+                    prev_randao: B256::random(),
+                    suggested_fee_recipient: alloy_primitives::Address::with_last_byte(idx as u8),
+                    withdrawals: Some(vec![]),
+                    parent_beacon_block_root: Some(B256::ZERO),
+                };
+                async move {
+                    let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                        env_ref.engine_client(idx).await?,
+                        fork_choice_state,
+                        Some(payload_attributes),
+                    )
+                    .await?;
+                    let payload_id = fcu_result.payload_id.ok_or_else(|| {
+                        eyre::eyre!("Node {idx}: No payload ID returned from forkchoiceUpdated")
+                    })?;
+
+                    sleep(build_time).await;
+
+                    let payload = EngineApiClient::<Engine>::get_payload_v3(
+                        env_ref.engine_client(idx).await?,
+                        payload_id,
+                    )
+                    .await?;
+                    Ok::<_, eyre::Report>((idx, payload))
+                }
+            });
+
+            let payloads = try_join_all(builds).await?;
+            debug!("Built {} competing payloads on top of {parent_hash}", payloads.len());
+            env.competing_payloads.extend(payloads);
+
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts a transaction has the expected EIP-2718 type.
+#[derive(Debug)]
+pub struct AssertTxType {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the transaction
+    pub tx_hash: B256,
+    /// Expected EIP-2718 transaction type
+    pub expected_type: u8,
+}
+
+impl AssertTxType {
+    /// Create a new `AssertTxType` action
+    pub const fn new(node_idx: usize, tx_hash: B256, expected_type: u8) -> Self {
+        Self { node_idx, tx_hash, expected_type }
+    }
+}
+
+impl<Engine> Action<Engine> for AssertTxType
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let tx = EthApiClient::<Transaction, Block, Receipt, Header>::transaction_by_hash(
+                &node_client.rpc,
+                self.tx_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Transaction {} not found", self.tx_hash))?;
+
+            let ty = tx.ty();
+            if ty != self.expected_type {
+                return Err(eyre::eyre!(
+                    "Transaction {} has type {ty}, expected {}",
+                    self.tx_hash,
+                    self.expected_type
+                ));
+            }
+
+            debug!("Transaction {} has expected type {ty}", self.tx_hash);
+            Ok(())
+        })
+    }
+}
+
+/// Action that records the chain tracking state of the [`Environment`] under a name.
+///
+/// The state can be restored later in the same run with [`RestoreCheckpoint`].
+#[derive(Debug)]
+pub struct Checkpoint {
+    /// Name of the checkpoint
+    pub name: String,
+}
+
+impl Checkpoint {
+    /// Create a new `Checkpoint` action
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<I> Action<I> for Checkpoint
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let snapshot = env.snapshot();
+            info!(
+                name = %self.name,
+                latest_block = ?snapshot.latest_block_info,
+                head = %snapshot.latest_fork_choice_state.head_block_hash,
+                "Reached checkpoint"
+            );
+            env.checkpoints.insert(self.name.clone(), snapshot);
+            Ok(())
+        })
+    }
+}
+
+/// Action that restores the chain tracking state of the [`Environment`] recorded by a
+/// [`Checkpoint`] with the same name.
+///
+/// Only the in-memory bookkeeping is restored, see [`Environment::restore`]. The chains of the
+/// nodes are not rewound: their head stays where it was until a forkchoice update moves it, e.g.
+/// with [`Reorg`] to the checkpoint's latest block, so blocks produced afterwards branch off the
+/// checkpoint.
+#[derive(Debug)]
+pub struct RestoreCheckpoint {
+    /// Name of the checkpoint
+    pub name: String,
+}
+
+impl RestoreCheckpoint {
+    /// Create a new `RestoreCheckpoint` action
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<I> Action<I> for RestoreCheckpoint
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let snapshot = env
+                .checkpoints
+                .get(&self.name)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("Unknown checkpoint: {}", self.name))?;
+            info!(
+                name = %self.name,
+                latest_block = ?snapshot.latest_block_info,
+                head = %snapshot.latest_fork_choice_state.head_block_hash,
+                "Restoring checkpoint"
+            );
+            env.restore(&snapshot);
+            Ok(())
+        })
+    }
+}
+
+/// Action that gracefully shuts down a node, restarts it on the same datadir and asserts that it
+/// reports the same head as before the restart.
+///
+/// The restarted node is reconnected to all other nodes of the cluster. Nodes only keep the blocks
+/// they have persisted, so the setup must persist every canonical block with
+/// [`Setup::with_persistence_threshold(0)`](crate::testsuite::setup::Setup::with_persistence_threshold),
+/// otherwise the head reverts to the last persisted block.
+#[derive(Debug)]
+pub struct ShutdownAndRestartNode {
+    /// The node index to restart
+    pub node_idx: usize,
+    /// Maximum time to wait for the node's tasks to shut down
+    pub shutdown_timeout: Duration,
+    /// Maximum time to wait for each peer connection to be re-established
+    pub connect_timeout: Duration,
+}
+
+impl ShutdownAndRestartNode {
+    /// Create a new `ShutdownAndRestartNode` action
+    pub const fn new(node_idx: usize) -> Self {
+        Self {
+            node_idx,
+            shutdown_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Set the maximum time to wait for the node to shut down
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for each peer connection to be re-established
+    pub const fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+}
+
+/// Shuts the node down, relaunches it on the same datadir and reconnects it to all other nodes of
+/// the cluster.
+///
+/// The relaunched node replaces the [`NodeClient`](crate::testsuite::NodeClient) at `node_idx`.
+async fn restart_node<I>(
+    env: &mut Environment<I>,
+    node_idx: usize,
+    shutdown_timeout: Duration,
+    connect_timeout: Duration,
+) -> Result<()> {
+    let launcher = env
+        .node_launcher
+        .clone()
+        .ok_or_else(|| eyre::eyre!("No node launcher available, was the setup applied?"))?;
+
+    let node_client = env
+        .node_clients
+        .get_mut(node_idx)
+        .ok_or_else(|| eyre::eyre!("Node index out of bounds: {node_idx}"))?;
+    let lifecycle = node_client
+        .lifecycle
+        .as_mut()
+        .ok_or_else(|| eyre::eyre!("Node {node_idx} was not launched by the setup"))?;
+    let storage = lifecycle.storage.clone();
+    if !lifecycle.shutdown(shutdown_timeout).await? {
+        debug!("Node {node_idx} did not shut down within {shutdown_timeout:?}");
+    }
+
+    let client = launcher.launch(node_idx, Some(storage)).await?;
+
+    for (peer_idx, peer) in env.node_clients.iter().enumerate() {
+        if peer_idx == node_idx {
+            continue
+        }
+        connect_nodes(&client, peer, connect_timeout).await.map_err(|e| {
+            eyre::eyre!("Failed to reconnect node {node_idx} to node {peer_idx}: {e}")
+        })?;
+    }
+
+    env.node_clients[node_idx] = client;
+    Ok(())
+}
+
+impl<I> Action<I> for ShutdownAndRestartNode
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let head_before = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &node_client.rpc,
+                BlockNumberOrTag::Latest,
+                false,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest block found before restart"))?
+            .header;
+
+            restart_node(env, self.node_idx, self.shutdown_timeout, self.connect_timeout).await?;
+
+            let head_after = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &env.node_clients[self.node_idx].rpc,
+                BlockNumberOrTag::Latest,
+                false,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest block found after restart"))?
+            .header;
+
+            if head_after.hash != head_before.hash {
+                return Err(eyre::eyre!(
+                    "Node {} head changed across restart: before {} ({}), after {} ({})",
+                    self.node_idx,
+                    head_before.number,
+                    head_before.hash,
+                    head_after.number,
+                    head_after.hash
+                ));
+            }
+
+            debug!(
+                "Node {} restarted with head {} ({})",
+                self.node_idx, head_after.number, head_after.hash
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Expectation on the `extra_data` of a block, used by [`AssertExtraData`].
+#[derive(Debug, Clone)]
+pub enum ExpectedExtraData {
+    /// The extra data must equal these bytes
+    Equals(Bytes),
+    /// The length of the extra data must satisfy this predicate
+    Length(fn(usize) -> bool),
+}
+
+/// Action that asserts the latest block's `extra_data` matches an expectation.
+#[derive(Debug)]
+pub struct AssertExtraData {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Expectation on the extra data
+    pub expected: ExpectedExtraData,
+}
+
+impl AssertExtraData {
+    /// Create a new `AssertExtraData` action asserting the extra data equals `extra_data`
+    pub fn equals(node_idx: usize, extra_data: impl Into<Bytes>) -> Self {
+        Self { node_idx, expected: ExpectedExtraData::Equals(extra_data.into()) }
+    }
+
+    /// Create a new `AssertExtraData` action asserting the length of the extra data satisfies
+    /// `predicate`
+    pub const fn length(node_idx: usize, predicate: fn(usize) -> bool) -> Self {
+        Self { node_idx, expected: ExpectedExtraData::Length(predicate) }
+    }
+}
+
+impl<Engine> Action<Engine> for AssertExtraData
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &node_client.rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found"))?;
+
+            let extra_data = &header.inner.extra_data;
+            match &self.expected {
+                ExpectedExtraData::Equals(expected) if extra_data != expected => {
+                    return Err(eyre::eyre!(
+                        "Block {} has extra data {extra_data}, expected {expected}",
+                        header.inner.number
+                    ));
+                }
+                ExpectedExtraData::Length(predicate) if !predicate(extra_data.len()) => {
+                    return Err(eyre::eyre!(
+                        "Block {} has extra data of unexpected length {}",
+                        header.inner.number,
+                        extra_data.len()
+                    ));
+                }
+                _ => {}
+            }
+
+            debug!("Block {} has expected extra data {extra_data}", header.inner.number);
+            Ok(())
+        })
+    }
+}
+
+/// Action that submits a payload to a node that is still syncing and asserts it transitions
+/// from `SYNCING` (or `ACCEPTED`) to `VALID`.
+///
+/// After submitting the payload, the node is polled with forkchoice updates pointing at the
+/// payload's block until it reports `VALID` or the timeout expires.
+#[derive(Debug)]
+pub struct AssertPayloadStatusTransition<Engine> {
+    /// The node index to submit the payload to
+    pub node_idx: usize,
+    /// The payload to submit
+    pub payload: ExecutionPayloadV3,
+    /// Parent beacon block root of the payload
+    pub parent_beacon_block_root: B256,
+    /// Maximum time to wait for the payload to become valid
+    pub timeout: Duration,
+    /// Interval between forkchoice updates
+    pub poll_interval: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertPayloadStatusTransition<Engine> {
+    /// Create a new `AssertPayloadStatusTransition` action
+    pub fn new(
+        node_idx: usize,
+        payload: ExecutionPayloadV3,
+        parent_beacon_block_root: B256,
+    ) -> Self {
+        Self {
+            node_idx,
+            payload,
+            parent_beacon_block_root,
+            timeout: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(500),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the maximum time to wait for the payload to become valid
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the interval between forkchoice updates
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for AssertPayloadStatusTransition<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let block_hash = self.payload.payload_inner.payload_inner.block_hash;
+
+            let status = EngineApiClient::<Engine>::new_payload_v3(
+                env.engine_client(self.node_idx).await?,
+                self.payload.clone(),
+                vec![],
+                self.parent_beacon_block_root,
+            )
+            .await?
+            .status;
+
+            if !matches!(status, PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted) {
+                return Err(eyre::eyre!(
+                    "Expected initial status SYNCING or ACCEPTED for payload {block_hash}, got {status:?}"
+                ));
+            }
+            debug!("Payload {block_hash} initially reported as {status:?}");
+
+            let fork_choice_state =
+                ForkchoiceState { head_block_hash: block_hash, ..env.latest_fork_choice_state };
+
+            let check = async {
+                loop {
+                    let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                        env.engine_client(self.node_idx).await?,
+                        fork_choice_state,
+                        None,
+                    )
+                    .await?
+                    .payload_status
+                    .status;
+
+                    match status {
+                        PayloadStatusEnum::Valid => return Ok(()),
+                        PayloadStatusEnum::Invalid { validation_error } => {
+                            return Err(eyre::eyre!(
+                                "Payload {block_hash} became invalid: {validation_error}"
+                            ))
+                        }
+                        status => debug!("Payload {block_hash} still reported as {status:?}"),
+                    }
+
+                    sleep(self.poll_interval).await;
+                }
+            };
+
+            tokio::time::timeout(self.timeout, check).await.map_err(|_| {
+                eyre::eyre!("Payload {block_hash} did not become valid within {:?}", self.timeout)
+            })??;
+
+            debug!("Payload {block_hash} transitioned to VALID");
+            Ok(())
+        })
+    }
+}
+
+/// Action that injects an artificial latency before engine API calls to a node.
+///
+/// The broadcast and payload building actions sleep for the injected latency before calling the
+/// node, which allows simulating a slow node. A zero latency removes the injected delay.
+#[derive(Debug)]
+pub struct LatencyInjector {
+    /// The node index to delay
+    pub node_idx: usize,
+    /// Delay applied before each engine API call to the node
+    pub latency: Duration,
+}
+
+impl LatencyInjector {
+    /// Create a new `LatencyInjector` action
+    pub const fn new(node_idx: usize, latency: Duration) -> Self {
+        Self { node_idx, latency }
+    }
+}
+
+impl<I> Action<I> for LatencyInjector
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+
+            if self.latency.is_zero() {
+                env.node_latencies.remove(&self.node_idx);
+            } else {
+                env.node_latencies.insert(self.node_idx, self.latency);
+            }
+
+            debug!("Injected latency of {:?} for node {}", self.latency, self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts a node's transaction pool has no pending or queued transactions.
+#[derive(Debug, Default)]
+pub struct AssertNoPendingTransactions {
+    /// The node index to query
+    pub node_idx: usize,
+}
+
+impl AssertNoPendingTransactions {
+    /// Create a new `AssertNoPendingTransactions` action
+    pub const fn new(node_idx: usize) -> Self {
+        Self { node_idx }
+    }
+}
+
+impl<I> Action<I> for AssertNoPendingTransactions
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let status = TxPoolApiClient::<Transaction>::txpool_status(&node_client.rpc).await?;
+
+            if status.pending != 0 || status.queued != 0 {
+                return Err(eyre::eyre!(
+                    "Node {} has transactions left in the pool: {} pending, {} queued",
+                    self.node_idx,
+                    status.pending,
+                    status.queued
+                ));
+            }
+
+            debug!("Node {} has no pending transactions", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// A branch of a [`ReorgScenario`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgBranch {
+    /// Number of the block on the canonical chain the branch forks off from, at the time the
+    /// branch is built
+    pub fork_block: u64,
+    /// Number of blocks built on the branch
+    pub length: u64,
+}
+
+/// Declarative description of a reorg scenario, replayed by [`ReplayReorgScenario`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgScenario {
+    /// Branches built in order, each one becomes the canonical chain once built
+    pub branches: Vec<ReorgBranch>,
+    /// Index of the branch that is made canonical at the end of the scenario
+    pub finalized_branch: usize,
+}
+
+/// Action that replays a [`ReorgScenario`] on all nodes.
+///
+/// Each branch is built as a chain of empty blocks on top of its fork block, read from the
+/// producer node. Every block is sent to all nodes with `engine_newPayloadV3` followed by a
+/// forkchoice update making it the head. At the end the tip of the finalized branch is made the
+/// head, and the action asserts that all nodes report it as their latest block.
+///
+/// The safe and finalized block are kept at the lowest fork block of the scenario, so that every
+/// branch can still be reorged. The head before the last reorg of the scenario is recorded in
+/// `env.pre_reorg_head` for [`AssertReorgDepth`].
+#[derive(Debug)]
+pub struct ReplayReorgScenario<Engine> {
+    /// The scenario to replay
+    pub scenario: ReorgScenario,
+    /// The node index the fork blocks are read from, recorded as the producer of the blocks
+    pub producer_idx: usize,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ReplayReorgScenario<Engine> {
+    /// Create a new `ReplayReorgScenario` action, reading the fork blocks from node 0
+    pub fn new(scenario: ReorgScenario) -> Self {
+        Self { scenario, producer_idx: 0, _phantom: Default::default() }
+    }
+
+    /// Set the node index the fork blocks are read from
+    pub const fn with_producer(mut self, producer_idx: usize) -> Self {
+        self.producer_idx = producer_idx;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for ReplayReorgScenario<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let producer = env
+                .node_clients
+                .get(self.producer_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.producer_idx))?;
+            if self.scenario.finalized_branch >= self.scenario.branches.len() {
+                return Err(eyre::eyre!(
+                    "Finalized branch out of bounds: {}",
+                    self.scenario.finalized_branch
+                ));
+            }
+
+            // every branch forks off at or above the lowest fork block, which stays canonical
+            let reorg_point = self
+                .scenario
+                .branches
+                .iter()
+                .map(|branch| branch.fork_block)
+                .min()
+                .ok_or_else(|| eyre::eyre!("Reorg scenario has no branches"))?;
+            let reorg_point_hash =
+                EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                    &producer.rpc,
+                    BlockNumberOrTag::Number(reorg_point),
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("No header found for fork block {reorg_point}"))?
+                .hash;
+
+            let latest = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &producer.rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found"))?;
+            let mut head = LatestBlockInfo { hash: latest.hash, number: latest.inner.number };
+            let mut pre_reorg_head = None;
+
+            let mut branch_tips = Vec::with_capacity(self.scenario.branches.len());
+            for (branch_idx, branch) in self.scenario.branches.iter().enumerate() {
+                let fork_header =
+                    EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                        &producer.rpc,
+                        BlockNumberOrTag::Number(branch.fork_block),
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        eyre::eyre!("No header found for fork block {}", branch.fork_block)
+                    })?;
+
+                let mut parent = fork_header.inner.clone();
+                let mut tip =
+                    LatestBlockInfo { hash: fork_header.hash, number: fork_header.inner.number };
+                for _ in 0..branch.length {
+                    let block = empty_block_on(
+                        &parent,
+                        tip.hash,
+                        parent.timestamp + env.block_timestamp_increment,
+                        Bytes::from(format!("branch {branch_idx}").into_bytes()),
+                    )?;
+                    let payload = ExecutionPayloadV3::from_block_slow(&block);
+                    let block_hash = payload.payload_inner.payload_inner.block_hash;
+                    let block_number = block.header.number;
+
+                    let fork_choice_state = ForkchoiceState {
+                        head_block_hash: block_hash,
+                        safe_block_hash: reorg_point_hash,
+                        finalized_block_hash: reorg_point_hash,
+                    };
+                    for idx in 0..env.node_clients.len() {
+                        let status = EngineApiClient::<Engine>::new_payload_v3(
+                            env.engine_client(idx).await?,
+                            payload.clone(),
+                            vec![],
+                            B256::ZERO,
+                        )
+                        .await?
+                        .status;
+                        if !status.is_valid() {
+                            return Err(eyre::eyre!(
+                                "Node {idx}: Branch {branch_idx} block {block_number} not accepted: {status:?}"
+                            ));
+                        }
+
+                        let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                            env.engine_client(idx).await?,
+                            fork_choice_state,
+                            None,
+                        )
+                        .await?
+                        .payload_status
+                        .status;
+                        if !status.is_valid() {
+                            return Err(eyre::eyre!(
+                                "Node {idx}: Forkchoice update to branch {branch_idx} block \
+                                 {block_number} returned {status:?}"
+                            ));
+                        }
+                    }
+
+                    // a block that does not extend the head reorged the head out
+                    if block.header.parent_hash != head.hash {
+                        pre_reorg_head = Some(head);
+                    }
+                    parent = block.header;
+                    tip = LatestBlockInfo { hash: block_hash, number: block_number };
+                    head = tip.clone();
+                }
+
+                debug!("Built branch {branch_idx} with tip {} ({})", tip.number, tip.hash);
+                branch_tips.push((tip, parent.timestamp));
+            }
+
+            let (tip, timestamp) = branch_tips[self.scenario.finalized_branch].clone();
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: tip.hash,
+                safe_block_hash: reorg_point_hash,
+                finalized_block_hash: reorg_point_hash,
+            };
+            for (idx, client) in env.node_clients.iter().enumerate() {
+                EngineApiClient::<Engine>::fork_choice_updated_v3(
+                    env.engine_client(idx).await?,
+                    fork_choice_state,
+                    None,
+                )
+                .await?;
+
+                let latest = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                    &client.rpc,
+                    BlockNumberOrTag::Latest,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("Node {idx}: No latest header found"))?;
+
+                if latest.hash != tip.hash {
+                    return Err(eyre::eyre!(
+                        "Node {idx}: Canonical head {} ({}) does not match branch {} tip {} ({})",
+                        latest.inner.number,
+                        latest.hash,
+                        self.scenario.finalized_branch,
+                        tip.number,
+                        tip.hash
+                    ));
+                }
+            }
+
+            debug!("Reorg scenario replayed, canonical head {} ({})", tip.number, tip.hash);
+            // the finalized branch reorged out the last branch, unless it is the last branch
+            if tip.hash != head.hash {
+                pre_reorg_head = Some(head);
+            }
+            if pre_reorg_head.is_some() {
+                env.pre_reorg_head = pre_reorg_head;
+            }
+            let fork_block = self.scenario.branches[self.scenario.finalized_branch].fork_block;
+            for number in fork_block + 1..=tip.number {
+                env.block_producers.insert(number, self.producer_idx);
+            }
+            env.latest_block_info = Some(tip);
+            env.latest_header_time = timestamp;
+            env.latest_fork_choice_state = fork_choice_state;
+            Ok(())
+        })
+    }
+}
+
+/// Ordering policy checked by [`AssertOrderingByPriorityFee`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFeeOrdering {
+    /// Effective tips must be non-increasing across all transactions of the block
+    #[default]
+    NonIncreasing,
+    /// Effective tips must be non-increasing between consecutive transactions of different
+    /// senders, consecutive transactions of the same sender are ordered by nonce instead
+    NonIncreasingAcrossSenders,
+}
+
+/// Action that asserts the transactions of a block are ordered by effective priority fee.
+#[derive(Debug)]
+pub struct AssertOrderingByPriorityFee {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The block to check
+    pub block: BlockNumberOrTag,
+    /// The ordering policy to check
+    pub ordering: PriorityFeeOrdering,
+}
+
+impl AssertOrderingByPriorityFee {
+    /// Create a new `AssertOrderingByPriorityFee` action for the given block
+    pub const fn new(node_idx: usize, block: BlockNumberOrTag) -> Self {
+        Self { node_idx, block, ordering: PriorityFeeOrdering::NonIncreasing }
+    }
+
+    /// Set the ordering policy to check
+    pub const fn with_ordering(mut self, ordering: PriorityFeeOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+}
+
+impl Default for AssertOrderingByPriorityFee {
+    fn default() -> Self {
+        Self::new(0, BlockNumberOrTag::Latest)
+    }
+}
+
+impl<Engine> Action<Engine> for AssertOrderingByPriorityFee
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let block = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &node_client.rpc,
+                self.block,
+                true,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No block found for {}", self.block))?;
+            let block_number = block.header.inner.number;
+            let base_fee = block.header.inner.base_fee_per_gas.unwrap_or_default();
+
+            let txs = block
+                .transactions
+                .into_transactions()
+                .map(|tx| {
+                    let tip =
+                        tx.effective_gas_price(Some(base_fee)).saturating_sub(base_fee as u128);
+                    (*tx.inner.tx_hash(), tx.inner.signer(), tip)
+                })
+                .collect::<Vec<_>>();
+
+            for pair in txs.windows(2) {
+                let (prev_hash, prev_sender, prev_tip) = pair[0];
+                let (hash, sender, tip) = pair[1];
+
+                if self.ordering == PriorityFeeOrdering::NonIncreasingAcrossSenders &&
+                    prev_sender == sender
+                {
+                    continue
+                }
+
+                if tip > prev_tip {
+                    return Err(eyre::eyre!(
+                        "Block {block_number}: transaction {hash} with tip {tip} is ordered after \
+                         transaction {prev_hash} with lower tip {prev_tip}"
+                    ));
+                }
+            }
+
+            debug!("Block {block_number}: {} transactions ordered by priority fee", txs.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that subscribes to `newPendingTransactions` on a node and asserts a set of transaction
+/// hashes is announced within a time window.
+///
+/// The subscription is opened when the action executes, so the transactions should be sent
+/// afterwards, e.g. by running this action concurrently with the one sending them.
+#[derive(Debug)]
+pub struct AssertPendingTransactionsAnnounced {
+    /// The node index to subscribe to
+    pub node_idx: usize,
+    /// Hashes expected to be announced
+    pub expected: Vec<B256>,
+    /// Duration to collect announced hashes for
+    pub duration: Duration,
+}
+
+impl AssertPendingTransactionsAnnounced {
+    /// Create a new `AssertPendingTransactionsAnnounced` action
+    pub const fn new(node_idx: usize, expected: Vec<B256>, duration: Duration) -> Self {
+        Self { node_idx, expected, duration }
+    }
+}
+
+impl<I> Action<I> for AssertPendingTransactionsAnnounced
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+            let ws = node_client
+                .ws
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("Node {} has no websocket client", self.node_idx))?;
+
+            let mut subscription: Subscription<B256> = ws
+                .subscribe(
+                    "eth_subscribe",
+                    rpc_params!["newPendingTransactions"],
+                    "eth_unsubscribe",
+                )
+                .await?;
+
+            let mut missing: HashSet<B256> = self.expected.iter().copied().collect();
+            let collect = async {
+                while !missing.is_empty() {
+                    match subscription.next().await {
+                        Some(hash) => {
+                            missing.remove(&hash?);
+                        }
+                        None => return Err(eyre::eyre!("Subscription closed by the node")),
+                    }
+                }
+                Ok(())
+            };
+
+            if let Ok(result) = tokio::time::timeout(self.duration, collect).await {
+                result?;
+            } else {
+                return Err(eyre::eyre!(
+                    "Node {} did not announce {} of {} expected transactions within {:?}: {:?}",
+                    self.node_idx,
+                    missing.len(),
+                    self.expected.len(),
+                    self.duration,
+                    missing
+                ));
+            }
+
+            debug!(
+                "Node {} announced all {} expected transactions",
+                self.node_idx,
+                self.expected.len()
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that recomputes a block's hash from its header and asserts it matches the hash
+/// reported by the node.
+#[derive(Debug)]
+pub struct AssertBlockHashConsistency {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The block to check
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertBlockHashConsistency {
+    /// Create a new `AssertBlockHashConsistency` action for the given block
+    pub const fn new(node_idx: usize, block: BlockNumberOrTag) -> Self {
+        Self { node_idx, block }
+    }
+}
+
+impl Default for AssertBlockHashConsistency {
+    fn default() -> Self {
+        Self::new(0, BlockNumberOrTag::Latest)
+    }
+}
+
+impl<Engine> Action<Engine> for AssertBlockHashConsistency
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &node_client.rpc,
+                self.block,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No header found for block {}", self.block))?;
+
+            let computed = header.inner.hash_slow();
+            if computed != header.hash {
+                return Err(eyre::eyre!(
+                    "Block {} hash mismatch: reported {}, computed {computed}",
+                    header.inner.number,
+                    header.hash
+                ));
+            }
+
+            debug!("Block {} hash {} matches the header", header.inner.number, header.hash);
+            Ok(())
+        })
+    }
+}
+
+/// A mutation of a block header field applied by [`FuzzPayloadMutations`] and
+/// [`GenerateInvalidPayload`].
+///
+/// The block hash is recomputed after applying a mutation, so the node has to execute or validate
+/// the block to reject it. All mutations except [`Self::FutureTimestamp`] make the block invalid
+/// for the execution layer, see [`Self::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadMutation {
+    /// Replaces the state root with a random hash
+    StateRoot,
+    /// Replaces the receipts root with a random hash
+    ReceiptsRoot,
+    /// Increments the gas used
+    GasUsed,
+    /// Sets the timestamp to zero, which is not greater than the parent's timestamp
+    Timestamp,
+    /// Sets the timestamp an hour ahead of the current time
+    ///
+    /// Post-merge the execution layer accepts blocks with a timestamp in the future, the slot
+    /// timing is enforced by the consensus layer. The block is only invalid pre-merge or at the
+    /// consensus layer, so this mutation is not rejected by the node and is not part of
+    /// [`Self::ALL`].
+    FutureTimestamp,
+    /// Sets the parent hash to the hash of a known block other than the parent, without changing
+    /// the block number
+    ParentHash,
+    /// Increments the blob gas used
+    BlobGasUsed,
+    /// Sets an extra data that exceeds the maximum length of 32 bytes
+    ExtraData,
+}
+
+impl PayloadMutation {
+    /// All mutations rejected by the execution layer, i.e. all except [`Self::FutureTimestamp`]
+    pub const ALL: [Self; 7] = [
+        Self::StateRoot,
+        Self::ReceiptsRoot,
+        Self::GasUsed,
+        Self::Timestamp,
+        Self::ParentHash,
+        Self::BlobGasUsed,
+        Self::ExtraData,
+    ];
+
+    /// Applies the mutation to the header.
+    ///
+    /// `known_hash` is the hash of a known block other than the parent, used by
+    /// [`Self::ParentHash`].
+    fn apply(self, header: &mut alloy_consensus::Header, known_hash: B256) {
+        match self {
+            Self::StateRoot => header.state_root = B256::random(),
+            Self::ReceiptsRoot => header.receipts_root = B256::random(),
+            Self::GasUsed => header.gas_used += 1,
+            Self::Timestamp => header.timestamp = 0,
+            Self::FutureTimestamp => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                header.timestamp = header.timestamp.max(now) + 3600;
+            }
+            Self::ParentHash => header.parent_hash = known_hash,
+            Self::BlobGasUsed => {
+                header.blob_gas_used = Some(header.blob_gas_used.unwrap_or_default() + 1)
+            }
+            Self::ExtraData => header.extra_data = Bytes::from_static(&[0xff; 33]),
+        }
+    }
+}
+
+/// The latest block of a node, together with the inputs needed to resubmit it as a payload.
+#[derive(Debug)]
+struct LatestPayloadInputs {
+    /// The latest block
+    block: reth_ethereum_primitives::Block,
+    /// Hash of the latest block
+    block_hash: B256,
+    /// Versioned hashes of the blobs of the block's transactions
+    versioned_hashes: Vec<B256>,
+    /// Parent beacon block root of the block
+    parent_beacon_block_root: B256,
+}
+
+impl LatestPayloadInputs {
+    /// Fetches the latest block with full transactions from the node.
+    async fn fetch(rpc: &HttpClient) -> Result<Self> {
+        let rpc_block = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+            rpc,
+            BlockNumberOrTag::Latest,
+            true,
+        )
+        .await?
+        .ok_or_else(|| eyre::eyre!("No latest block found from rpc"))?;
+        let block_hash = rpc_block.header.hash;
+        let parent_beacon_block_root = rpc_block
+            .header
+            .inner
+            .parent_beacon_block_root
+            .ok_or_else(|| eyre::eyre!("No parent beacon block root for latest block"))?;
+
+        let transactions = rpc_block.transactions.into_transactions().collect::<Vec<_>>();
+        let versioned_hashes = transactions
+            .iter()
+            .filter_map(|tx| tx.blob_versioned_hashes())
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        let block = reth_ethereum_primitives::Block {
+            header: rpc_block.header.inner,
+            body: reth_ethereum_primitives::BlockBody {
+                transactions: transactions
+                    .into_iter()
+                    .map(|tx| tx.inner.into_inner().into())
+                    .collect(),
+                ommers: Default::default(),
+                withdrawals: rpc_block.withdrawals,
+            },
+        };
+
+        Ok(Self { block, block_hash, versioned_hashes, parent_beacon_block_root })
+    }
+}
+
+/// Action that resubmits the latest block with each of a set of header mutations applied and
+/// asserts the node rejects every mutated payload as `INVALID`.
+///
+/// All mutations that are not rejected are collected and reported together. By default the
+/// mutations of [`PayloadMutation::ALL`] are applied, [`PayloadMutation::FutureTimestamp`] is
+/// accepted by the node and fails this action.
+#[derive(Debug)]
+pub struct FuzzPayloadMutations {
+    /// The node index to submit the payloads to
+    pub node_idx: usize,
+    /// The mutations to apply
+    pub mutations: Vec<PayloadMutation>,
+}
+
+impl FuzzPayloadMutations {
+    /// Create a new `FuzzPayloadMutations` action with the given mutations
+    pub const fn new(node_idx: usize, mutations: Vec<PayloadMutation>) -> Self {
+        Self { node_idx, mutations }
+    }
+}
+
+impl Default for FuzzPayloadMutations {
+    fn default() -> Self {
+        Self::new(0, PayloadMutation::ALL.to_vec())
+    }
+}
+
+impl<Engine> Action<Engine> for FuzzPayloadMutations
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let LatestPayloadInputs {
+                block,
+                block_hash,
+                versioned_hashes,
+                parent_beacon_block_root,
+            } = LatestPayloadInputs::fetch(&node_client.rpc).await?;
+
+            let mut accepted = Vec::new();
+            for mutation in &self.mutations {
+                let mut mutated = block.clone();
+                mutation.apply(&mut mutated.header, block_hash);
+
+                let status = EngineApiClient::<Engine>::new_payload_v3(
+                    env.engine_client(self.node_idx).await?,
+                    ExecutionPayloadV3::from_block_slow(&mutated),
+                    versioned_hashes.clone(),
+                    parent_beacon_block_root,
+                )
+                .await?
+                .status;
+
+                if status.is_invalid() {
+                    debug!("Mutation {mutation:?} rejected: {status:?}");
+                } else {
+                    accepted.push((*mutation, status));
+                }
+            }
+
+            if !accepted.is_empty() {
+                return Err(eyre::eyre!(
+                    "Node {} did not reject {} of {} mutated payloads: {accepted:?}",
+                    self.node_idx,
+                    accepted.len(),
+                    self.mutations.len()
+                ));
+            }
+
+            debug!("Node {} rejected all {} mutated payloads", self.node_idx, self.mutations.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that produces the next block on a specific node and makes it canonical on all nodes.
+///
+/// Unlike [`PickNextBlockProducer`], the producer is chosen by the caller and stored in
+/// `env.last_producer_idx`. The payload built by the producer is sent to every node, or only to
+/// the nodes set with `with_targets`, with `engine_newPayloadV3`, followed by a forkchoice update
+/// making it the head.
+#[derive(Debug)]
+pub struct ProduceBlockWithSpecificProducer<Engine> {
+    /// The node index building the block
+    pub producer_idx: usize,
+    /// Time given to the producer to build the payload
+    pub build_time: Duration,
+    /// The node indices the payload is sent to, all nodes if unset
+    pub targets: Option<Vec<usize>>,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ProduceBlockWithSpecificProducer<Engine> {
+    /// Create a new `ProduceBlockWithSpecificProducer` action
+    pub fn new(producer_idx: usize) -> Self {
+        Self {
+            producer_idx,
+            build_time: Duration::from_secs(1),
+            targets: None,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Send the payload only to the given node indices
+    pub fn with_targets(mut self, targets: Vec<usize>) -> Self {
+        self.targets = Some(targets);
+        self
+    }
+
+    /// Set the time given to the producer to build the payload
+    pub const fn with_build_time(mut self, build_time: Duration) -> Self {
+        self.build_time = build_time;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for ProduceBlockWithSpecificProducer<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.producer_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.producer_idx));
+            }
+            env.last_producer_idx = Some(self.producer_idx);
+
+            GeneratePayloadAttributes::default().execute(env).await?;
+
+            let latest_block = env
+                .latest_block_info
+                .clone()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let payload_attributes = env
+                .payload_attributes
+                .get(&(latest_block.number + 1))
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for next block"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let payload_id = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(self.producer_idx).await?,
+                fork_choice_state,
+                Some(payload_attributes.clone()),
+            )
+            .await?
+            .payload_id
+            .ok_or_else(|| eyre::eyre!("No payload ID returned from forkchoiceUpdated"))?;
+
+            sleep(self.build_time).await;
+
+            let payload = EngineApiClient::<Engine>::get_payload_v3(
+                env.engine_client(self.producer_idx).await?,
+                payload_id,
+            )
+            .await?
+            .execution_payload;
+            let block_hash = payload.payload_inner.payload_inner.block_hash;
+            let block_number = payload.payload_inner.payload_inner.block_number;
+            let parent_beacon_block_root =
+                payload_attributes.parent_beacon_block_root.unwrap_or_default();
+
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: block_hash,
+                safe_block_hash: block_hash,
+                finalized_block_hash: block_hash,
+            };
+            let targets =
+                self.targets.clone().unwrap_or_else(|| (0..env.node_clients.len()).collect());
+            for idx in targets {
+                let status = EngineApiClient::<Engine>::new_payload_v3(
+                    env.engine_client(idx).await?,
+                    payload.clone(),
+                    vec![],
+                    parent_beacon_block_root,
+                )
+                .await?
+                .status;
+                if !status.is_valid() {
+                    return Err(eyre::eyre!(
+                        "Node {idx}: Block {block_number} from producer {} not accepted: {status:?}",
+                        self.producer_idx
+                    ));
+                }
+
+                EngineApiClient::<Engine>::fork_choice_updated_v3(
+                    env.engine_client(idx).await?,
+                    fork_choice_state,
+                    None,
+                )
+                .await?;
+            }
+
+            debug!("Node {} produced block {block_number} ({block_hash})", self.producer_idx);
+            env.payload_id_history.insert(block_number, payload_id);
+            env.block_producers.insert(block_number, self.producer_idx);
+            env.latest_block_info =
+                Some(LatestBlockInfo { hash: block_hash, number: block_number });
+            env.latest_header_time = payload_attributes.timestamp;
+            env.latest_fork_choice_state = fork_choice_state;
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the `requests_hash` of a Prague block commits to its execution requests.
+///
+/// The action resolves a built payload with `engine_getPayloadV4`, imports it on the node with
+/// `engine_newPayloadV4`, makes it the canonical head with `engine_forkchoiceUpdatedV3` and
+/// compares the `requests_hash` of the stored header with the hash recomputed from the execution
+/// requests. The payload must not have been resolved before.
+#[derive(Debug)]
+pub struct AssertRequestsHash<Engine> {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The payload to check, defaults to `env.next_payload_id`
+    pub payload_id: Option<PayloadId>,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertRequestsHash<Engine> {
+    /// Create a new `AssertRequestsHash` action for the next payload
+    pub fn new(node_idx: usize) -> Self {
+        Self { node_idx, payload_id: None, _phantom: Default::default() }
+    }
+
+    /// Set the payload to check
+    pub const fn with_payload_id(mut self, payload_id: PayloadId) -> Self {
+        self.payload_id = Some(payload_id);
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for AssertRequestsHash<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV4 = ExecutionPayloadEnvelopeV4>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let payload_id = self
+                .payload_id
+                .or(env.next_payload_id)
+                .ok_or_else(|| eyre::eyre!("No payload ID available"))?;
+
+            let envelope = EngineApiClient::<Engine>::get_payload_v4(
+                env.engine_client(self.node_idx).await?,
+                payload_id,
+            )
+            .await?;
+            let payload = envelope.envelope_inner.execution_payload;
+            let block_hash = payload.payload_inner.payload_inner.block_hash;
+            let block_number = payload.payload_inner.payload_inner.block_number;
+            let computed = envelope.execution_requests.requests_hash();
+
+            let parent_beacon_block_root = env
+                .payload_attributes
+                .get(&block_number)
+                .and_then(|attributes| attributes.parent_beacon_block_root)
+                .unwrap_or_default();
+            let status = EngineApiClient::<Engine>::new_payload_v4(
+                env.engine_client(self.node_idx).await?,
+                payload,
+                vec![],
+                parent_beacon_block_root,
+                RequestsOrHash::Requests(envelope.execution_requests),
+            )
+            .await?
+            .status;
+            if !status.is_valid() {
+                return Err(eyre::eyre!("Block {block_number} not accepted: {status:?}"));
+            }
+
+            // the header is only served once the block is canonical
+            let fork_choice_state =
+                ForkchoiceState { head_block_hash: block_hash, ..env.latest_fork_choice_state };
+            let fcu_status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(self.node_idx).await?,
+                fork_choice_state,
+                None,
+            )
+            .await?
+            .payload_status
+            .status;
+            if !fcu_status.is_valid() {
+                return Err(eyre::eyre!("Block {block_number} not made canonical: {fcu_status:?}"));
+            }
+            env.latest_block_info =
+                Some(LatestBlockInfo { hash: block_hash, number: block_number });
+            env.latest_fork_choice_state = fork_choice_state;
+
+            let node_client = &env.node_clients[self.node_idx];
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(
+                &node_client.rpc,
+                block_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No header found for block {block_hash}"))?;
+
+            match header.inner.requests_hash {
+                Some(requests_hash) if requests_hash == computed => {}
+                requests_hash => {
+                    return Err(eyre::eyre!(
+                        "Block {block_number} requests hash mismatch: header {requests_hash:?}, computed {computed}"
+                    ));
+                }
+            }
+
+            debug!("Block {block_number} requests hash {computed} matches its requests");
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts an account does not exist, i.e. it has zero balance, zero nonce and no
+/// code.
+#[derive(Debug)]
+pub struct AssertAccountNonexistent {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The address of the account
+    pub address: Address,
+    /// The block to check the account at
+    pub block: BlockId,
+}
+
+impl AssertAccountNonexistent {
+    /// Create a new `AssertAccountNonexistent` action checking the account at the latest block
+    pub const fn new(node_idx: usize, address: Address) -> Self {
+        Self { node_idx, address, block: BlockId::Number(BlockNumberOrTag::Latest) }
+    }
+
+    /// Set the block to check the account at
+    pub const fn at_block(mut self, block: BlockId) -> Self {
+        self.block = block;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for AssertAccountNonexistent
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+            let rpc = &node_client.rpc;
+
+            let balance = EthApiClient::<Transaction, Block, Receipt, Header>::balance(
+                rpc,
+                self.address,
+                Some(self.block),
+            )
+            .await?;
+            if !balance.is_zero() {
+                return Err(eyre::eyre!(
+                    "Account {} exists at {}: balance is {balance}",
+                    self.address,
+                    self.block
+                ));
+            }
+
+            let nonce = EthApiClient::<Transaction, Block, Receipt, Header>::transaction_count(
+                rpc,
+                self.address,
+                Some(self.block),
+            )
+            .await?;
+            if !nonce.is_zero() {
+                return Err(eyre::eyre!(
+                    "Account {} exists at {}: nonce is {nonce}",
+                    self.address,
+                    self.block
+                ));
+            }
+
+            let code = EthApiClient::<Transaction, Block, Receipt, Header>::get_code(
+                rpc,
+                self.address,
+                Some(self.block),
+            )
+            .await?;
+            if !code.is_empty() {
+                return Err(eyre::eyre!(
+                    "Account {} exists at {}: code is {code}",
+                    self.address,
+                    self.block
+                ));
+            }
+
+            debug!("Account {} does not exist at {}", self.address, self.block);
+            Ok(())
+        })
+    }
+}
+
+/// Action that produces blocks and periodically reorgs the chain, as a soak test.
+///
+/// Every `reorg_every` blocks, the chain is reset to the block `reorg_depth` blocks below the
+/// head and a competing branch of `reorg_depth + 1` blocks is built on top of it, which becomes
+/// canonical. After each reorg the action asserts that all nodes converged to the new head. The
+/// head before the last reorg is recorded in `env.pre_reorg_head` for [`AssertReorgDepth`].
+///
+/// Blocks are built by the producer node and imported on all nodes. A node does not build a payload
+/// on top of an ancestor of its head, so the blocks of the competing branches are built as empty
+/// blocks by the action instead. The forkchoice updates sent by this action leave the safe and
+/// finalized blocks unset, so the reorged blocks are never finalized.
+#[derive(Debug)]
+pub struct ProduceBlocksWithReorgEvery<Engine> {
+    /// Total number of blocks to produce, not counting the blocks of the competing branches
+    pub num_blocks: u64,
+    /// Number of blocks between reorgs
+    pub reorg_every: u64,
+    /// Number of blocks reorged out by each reorg
+    pub reorg_depth: u64,
+    /// The node index building the blocks
+    pub producer_idx: usize,
+    /// Time given to the producer to build each payload
+    pub build_time: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ProduceBlocksWithReorgEvery<Engine> {
+    /// Create a new `ProduceBlocksWithReorgEvery` action reorging out one block every
+    /// `reorg_every` blocks
+    pub fn new(num_blocks: u64, reorg_every: u64) -> Self {
+        Self {
+            num_blocks,
+            reorg_every,
+            reorg_depth: 1,
+            producer_idx: 0,
+            build_time: Duration::from_secs(1),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the number of blocks reorged out by each reorg
+    pub const fn with_reorg_depth(mut self, reorg_depth: u64) -> Self {
+        self.reorg_depth = reorg_depth;
+        self
+    }
+
+    /// Set the node index building the blocks
+    pub const fn with_producer(mut self, producer_idx: usize) -> Self {
+        self.producer_idx = producer_idx;
+        self
+    }
+
+    /// Set the time given to the producer to build each payload
+    pub const fn with_build_time(mut self, build_time: Duration) -> Self {
+        self.build_time = build_time;
+        self
+    }
+
+    /// Builds a block on top of the head `parent` on the producer and makes it the head of all
+    /// nodes.
+    async fn build_on(
+        &self,
+        env: &Environment<Engine>,
+        parent: &LatestBlockInfo,
+        timestamp: u64,
+    ) -> Result<LatestBlockInfo>
+    where
+        Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+            + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+    {
+        let payload_attributes = PayloadAttributes {
+            timestamp,
+            prev_randao: B256::random(),
+            suggested_fee_recipient: Address::random(),
+            withdrawals: Some(vec![]),
+            parent_beacon_block_root: Some(B256::ZERO),
+        };
+        let fork_choice_state = ForkchoiceState {
+            head_block_hash: parent.hash,
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+
+        let payload_id = EngineApiClient::<Engine>::fork_choice_updated_v3(
+            env.engine_client(self.producer_idx).await?,
+            fork_choice_state,
+            Some(payload_attributes),
+        )
+        .await?
+        .payload_id
+        .ok_or_else(|| eyre::eyre!("No payload ID returned from forkchoiceUpdated"))?;
+
+        sleep(self.build_time).await;
+
+        let payload = EngineApiClient::<Engine>::get_payload_v3(
+            env.engine_client(self.producer_idx).await?,
+            payload_id,
+        )
+        .await?
+        .execution_payload;
+
+        self.import(env, payload).await
+    }
+
+    /// Imports a block on all nodes and makes it their head.
+    async fn import(
+        &self,
+        env: &Environment<Engine>,
+        payload: ExecutionPayloadV3,
+    ) -> Result<LatestBlockInfo>
+    where
+        Engine: EngineTypes,
+    {
+        let block = LatestBlockInfo {
+            hash: payload.payload_inner.payload_inner.block_hash,
+            number: payload.payload_inner.payload_inner.block_number,
+        };
+
+        let fork_choice_state = ForkchoiceState {
+            head_block_hash: block.hash,
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        for idx in 0..env.node_clients.len() {
+            let status = EngineApiClient::<Engine>::new_payload_v3(
+                env.engine_client(idx).await?,
+                payload.clone(),
+                vec![],
+                B256::ZERO,
+            )
+            .await?
+            .status;
+            if !status.is_valid() {
+                return Err(eyre::eyre!(
+                    "Node {idx}: Block {} not accepted: {status:?}",
+                    block.number
+                ));
+            }
+
+            EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(idx).await?,
+                fork_choice_state,
+                None,
+            )
+            .await?;
+        }
+
+        Ok(block)
+    }
+}
+
+impl<Engine> Action<Engine> for ProduceBlocksWithReorgEvery<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.producer_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.producer_idx));
+            }
+            let head = env
+                .latest_block_info
+                .clone()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+
+            // canonical blocks produced so far together with their timestamps
+            let mut chain = vec![(head, env.latest_header_time)];
+            let mut pre_reorg_head = None;
+
+            for produced in 1..=self.num_blocks {
+                let (parent, parent_timestamp) = chain.last().cloned().expect("chain is not empty");
+                let block = self
+                    .build_on(env, &parent, parent_timestamp + env.block_timestamp_increment)
+                    .await?;
+                chain.push((block, parent_timestamp + env.block_timestamp_increment));
+
+                let reorg_due = self.reorg_every > 0 && produced % self.reorg_every == 0;
+                if !reorg_due || chain.len() as u64 <= self.reorg_depth {
+                    continue
+                }
+
+                pre_reorg_head = chain.last().map(|(head, _)| head.clone());
+                chain.truncate(chain.len() - self.reorg_depth as usize);
+                let (fork_block, _) = chain.last().cloned().expect("chain is not empty");
+                let mut parent =
+                    EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(
+                        &env.node_clients[self.producer_idx].rpc,
+                        fork_block.hash,
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        eyre::eyre!("No header found for fork block {}", fork_block.hash)
+                    })?
+                    .inner;
+                let mut parent_hash = fork_block.hash;
+                for _ in 0..=self.reorg_depth {
+                    let block = empty_block_on(
+                        &parent,
+                        parent_hash,
+                        parent.timestamp + env.block_timestamp_increment,
+                        Bytes::from_static(b"reorg"),
+                    )?;
+                    let imported =
+                        self.import(env, ExecutionPayloadV3::from_block_slow(&block)).await?;
+                    chain.push((imported.clone(), block.header.timestamp));
+                    parent = block.header;
+                    parent_hash = imported.hash;
+                }
+
+                let (tip, _) = chain.last().cloned().expect("chain is not empty");
+                for (idx, client) in env.node_clients.iter().enumerate() {
+                    let latest =
+                        EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                            &client.rpc,
+                            BlockNumberOrTag::Latest,
+                        )
+                        .await?
+                        .ok_or_else(|| eyre::eyre!("Node {idx}: No latest header found"))?;
+                    if latest.hash != tip.hash {
+                        return Err(eyre::eyre!(
+                            "Node {idx}: Head {} ({}) did not converge to {} ({}) after reorg",
+                            latest.inner.number,
+                            latest.hash,
+                            tip.number,
+                            tip.hash
+                        ));
+                    }
+                }
+                debug!(
+                    "Reorged {} blocks on top of block {}, new head {} ({})",
+                    self.reorg_depth, fork_block.number, tip.number, tip.hash
+                );
+            }
+
+            for (block, _) in &chain[1..] {
+                env.block_producers.insert(block.number, self.producer_idx);
+            }
+            if pre_reorg_head.is_some() {
+                env.pre_reorg_head = pre_reorg_head;
+            }
+            let (head, timestamp) = chain.pop().expect("chain is not empty");
+            env.latest_fork_choice_state = ForkchoiceState {
+                head_block_hash: head.hash,
+                safe_block_hash: B256::ZERO,
+                finalized_block_hash: B256::ZERO,
+            };
+            env.latest_block_info = Some(head);
+            env.latest_header_time = timestamp;
+            Ok(())
+        })
+    }
+}
+
+/// Action that queries logs with `eth_getLogs` and asserts the matching logs meet expectations.
+///
+/// The [`Filter`] carries the full `eth_getLogs` semantics: a block range, a set of addresses,
+/// and per position topic sets where an empty set is a wildcard and multiple topics are OR-ed.
+#[derive(Debug)]
+pub struct AssertLogsMatch {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The filter to query logs with
+    pub filter: Filter,
+    /// Expected number of matching logs
+    pub expected_count: Option<usize>,
+    /// Expected matching logs, compared by address, topics and data in order
+    pub expected_logs: Option<Vec<alloy_primitives::Log>>,
+}
+
+impl AssertLogsMatch {
+    /// Create a new `AssertLogsMatch` action
+    pub const fn new(node_idx: usize, filter: Filter) -> Self {
+        Self { node_idx, filter, expected_count: None, expected_logs: None }
+    }
+
+    /// Expect the given number of matching logs
+    pub const fn with_count(mut self, count: usize) -> Self {
+        self.expected_count = Some(count);
+        self
+    }
+
+    /// Expect no matching logs
+    pub const fn expect_empty(self) -> Self {
+        self.with_count(0)
+    }
+
+    /// Expect exactly the given logs, in order
+    pub fn with_logs(mut self, logs: Vec<alloy_primitives::Log>) -> Self {
+        self.expected_logs = Some(logs);
+        self
+    }
+}
+
+impl<I> Action<I> for AssertLogsMatch
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let logs =
+                EthFilterApiClient::<Transaction>::logs(&node_client.rpc, self.filter.clone())
+                    .await?;
+
+            if let Some(expected_count) = self.expected_count {
+                if logs.len() != expected_count {
+                    return Err(eyre::eyre!(
+                        "Expected {expected_count} matching logs, got {}: {logs:?}",
+                        logs.len()
+                    ));
+                }
+            }
+
+            if let Some(expected_logs) = &self.expected_logs {
+                if logs.len() != expected_logs.len() {
+                    return Err(eyre::eyre!(
+                        "Expected {} matching logs, got {}",
+                        expected_logs.len(),
+                        logs.len()
+                    ));
+                }
+                for (idx, (log, expected)) in logs.iter().zip(expected_logs).enumerate() {
+                    if &log.inner != expected {
+                        return Err(eyre::eyre!(
+                            "Log {idx} mismatch: expected {expected:?}, got {:?}",
+                            log.inner
+                        ));
+                    }
+                }
+            }
+
+            debug!("Found {} logs matching the filter", logs.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that builds the next payload like [`GenerateNextPayload`] and asserts the time between
+/// the first forkchoice update with payload attributes and `engine_getPayloadV3` returning a
+/// payload with transactions stays within a budget.
+///
+/// Resolving a payload ends its build job, so the action polls by sending the forkchoice update
+/// again, giving the builder `poll_interval` and resolving the payload, until the payload contains
+/// transactions or the budget is exhausted. The node's pool must hold pending transactions.
+#[derive(Debug)]
+pub struct WaitForPayloadBuildTime {
+    /// The node index building the payload
+    pub node_idx: usize,
+    /// Maximum allowed payload build time
+    pub budget: Duration,
+    /// Time given to the builder before each attempt to resolve the payload
+    pub poll_interval: Duration,
+}
+
+impl WaitForPayloadBuildTime {
+    /// Create a new `WaitForPayloadBuildTime` action building on node 0
+    pub const fn new(budget: Duration) -> Self {
+        Self { node_idx: 0, budget, poll_interval: Duration::from_millis(50) }
+    }
+
+    /// Set the node index building the payload
+    pub const fn with_node(mut self, node_idx: usize) -> Self {
+        self.node_idx = node_idx;
+        self
+    }
+
+    /// Set the time given to the builder before each attempt to resolve the payload
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for WaitForPayloadBuildTime
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+    reth_node_ethereum::engine::EthPayloadAttributes:
+        From<<Engine as EngineTypes>::ExecutionPayloadEnvelopeV3>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+
+            let start = Instant::now();
+            let deadline = start + self.budget;
+            let (payload_id, envelope) = loop {
+                let payload_id = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                    env.engine_client(self.node_idx).await?,
+                    fork_choice_state,
+                    Some(payload_attributes.clone()),
+                )
+                .await?
+                .payload_id
+                .ok_or_else(|| eyre::eyre!("No payload ID returned from forkchoiceUpdated"))?;
+                sleep(self.poll_interval).await;
+                let envelope = EngineApiClient::<Engine>::get_payload_v3(
+                    env.engine_client(self.node_idx).await?,
+                    payload_id,
+                )
+                .await?;
+
+                let has_transactions =
+                    !envelope.execution_payload.payload_inner.payload_inner.transactions.is_empty();
+                if has_transactions || Instant::now() >= deadline {
+                    break (payload_id, envelope)
+                }
+            };
+            let elapsed = start.elapsed();
+
+            let transactions =
+                envelope.execution_payload.payload_inner.payload_inner.transactions.len();
+            if transactions == 0 {
+                return Err(eyre::eyre!(
+                    "Payload {payload_id} contained no transactions within the budget of {:?}",
+                    self.budget
+                ));
+            }
+            if elapsed > self.budget {
+                return Err(eyre::eyre!(
+                    "Payload build took {elapsed:?}, exceeding the budget of {:?}",
+                    self.budget
+                ));
+            }
+
+            debug!("Payload {payload_id} with {transactions} transactions built in {elapsed:?}");
+            let built_payload: PayloadAttributes = envelope.into();
+            env.next_payload_id = Some(payload_id);
+            env.payload_id_history.insert(latest_block.number + 1, payload_id);
+            env.block_producers.insert(latest_block.number + 1, self.node_idx);
+            env.latest_payload_built = Some(built_payload);
+            Ok(())
+        })
+    }
+}
+
+/// Action that produces a block with a target gas limit and asserts its gas limit moved toward
+/// the target while respecting the per-block adjustment bound of `parent_gas_limit / 1024`.
+///
+/// The producer must have been launched with the target as the builder's gas limit, see
+/// [`Setup::with_builder_gas_limit`](crate::testsuite::setup::Setup::with_builder_gas_limit).
+/// With `with_miner_api` the target is requested with `miner_setGasLimit` instead, and the action
+/// fails if the node does not apply it.
+#[derive(Debug)]
+pub struct MineBlockWithGasLimitTarget<Engine> {
+    /// The node index producing the block
+    pub producer_idx: usize,
+    /// The gas limit to target
+    pub target_gas_limit: u64,
+    /// Whether to request the target with `miner_setGasLimit`
+    pub use_miner_api: bool,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> MineBlockWithGasLimitTarget<Engine> {
+    /// Create a new `MineBlockWithGasLimitTarget` action
+    pub fn new(producer_idx: usize, target_gas_limit: u64) -> Self {
+        Self { producer_idx, target_gas_limit, use_miner_api: false, _phantom: Default::default() }
+    }
+
+    /// Request the target with `miner_setGasLimit` before producing the block
+    pub const fn with_miner_api(mut self) -> Self {
+        self.use_miner_api = true;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for MineBlockWithGasLimitTarget<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.producer_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.producer_idx))?;
+
+            if self.use_miner_api {
+                let applied = MinerApiClient::set_gas_limit(
+                    &node_client.rpc,
+                    U128::from(self.target_gas_limit),
+                )
+                .await?;
+                if !applied {
+                    return Err(eyre::eyre!(
+                        "Node {} did not apply the gas limit target {}",
+                        self.producer_idx,
+                        self.target_gas_limit
+                    ));
+                }
+            }
+
+            let parent_gas_limit =
+                EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                    &node_client.rpc,
+                    BlockNumberOrTag::Latest,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("No latest header found"))?
+                .inner
+                .gas_limit;
+
+            ProduceBlockWithSpecificProducer::<Engine>::new(self.producer_idx).execute(env).await?;
+
+            let gas_limit = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &env.node_clients[self.producer_idx].rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found"))?
+            .inner
+            .gas_limit;
+
+            let max_delta = parent_gas_limit / 1024;
+            let within_bound = gas_limit.abs_diff(parent_gas_limit) < max_delta;
+            let toward_target = if self.target_gas_limit >= parent_gas_limit {
+                (parent_gas_limit..=self.target_gas_limit).contains(&gas_limit)
+            } else {
+                (self.target_gas_limit..=parent_gas_limit).contains(&gas_limit)
+            };
+            if !within_bound || !toward_target {
+                return Err(eyre::eyre!(
+                    "Gas limit {gas_limit} does not move from parent {parent_gas_limit} toward \
+                     requested {} within the bound of {max_delta}",
+                    self.target_gas_limit
+                ));
+            }
+
+            debug!(
+                "Gas limit moved from {parent_gas_limit} to {gas_limit} toward requested {}",
+                self.target_gas_limit
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts a transaction is included at the expected index within its block.
+#[derive(Debug)]
+pub struct AssertTransactionIndexInBlock {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the transaction
+    pub tx_hash: B256,
+    /// Expected index of the transaction within its block
+    pub expected_index: u64,
+}
+
+impl AssertTransactionIndexInBlock {
+    /// Create a new `AssertTransactionIndexInBlock` action
+    pub const fn new(node_idx: usize, tx_hash: B256, expected_index: u64) -> Self {
+        Self { node_idx, tx_hash, expected_index }
+    }
+}
+
+impl<Engine> Action<Engine> for AssertTransactionIndexInBlock
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let receipt =
+                EthApiClient::<Transaction, Block, TransactionReceipt, Header>::transaction_receipt(
+                    &node_client.rpc,
+                    self.tx_hash,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("No receipt found for transaction {}", self.tx_hash))?;
+
+            let index = receipt
+                .transaction_index
+                .ok_or_else(|| eyre::eyre!("Transaction {} is not included", self.tx_hash))?;
+            if index != self.expected_index {
+                return Err(eyre::eyre!(
+                    "Transaction {} is at index {index} in block {:?}, expected {}",
+                    self.tx_hash,
+                    receipt.block_number,
+                    self.expected_index
+                ));
+            }
+
+            debug!("Transaction {} is at expected index {index}", self.tx_hash);
+            Ok(())
+        })
+    }
+}
+
+/// Action that has one node propose invalid blocks and asserts the honest nodes reject them and
+/// ban the misbehaving node.
+///
+/// Each invalid block is the latest block with a corrupted state root. It is sent to all other
+/// nodes, which must reject it as `INVALID` and keep their head. The engine API does not know
+/// which peer a payload came from, so every rejected block is reported to the honest node's peer
+/// manager as a bad block from the misbehaving node, like the network does for a failed block
+/// import. The action then asserts the honest nodes banned the misbehaving node and dropped it as
+/// a peer.
+#[derive(Debug)]
+pub struct SlashingScenario<Engine> {
+    /// The node index proposing the invalid blocks
+    pub misbehaving_idx: usize,
+    /// Number of invalid blocks the misbehaving node proposes
+    pub invalid_blocks: usize,
+    /// Maximum time to wait for the misbehaving node to be dropped
+    pub disconnect_timeout: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> SlashingScenario<Engine> {
+    /// Create a new `SlashingScenario` action
+    ///
+    /// By default the misbehaving node proposes enough invalid blocks to be banned.
+    pub fn new(misbehaving_idx: usize) -> Self {
+        Self {
+            misbehaving_idx,
+            invalid_blocks: 4,
+            disconnect_timeout: Duration::from_secs(10),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the number of invalid blocks the misbehaving node proposes
+    pub const fn with_invalid_blocks(mut self, invalid_blocks: usize) -> Self {
+        self.invalid_blocks = invalid_blocks;
+        self
+    }
+
+    /// Set the maximum time to wait for the misbehaving node to be dropped
+    pub const fn with_disconnect_timeout(mut self, disconnect_timeout: Duration) -> Self {
+        self.disconnect_timeout = disconnect_timeout;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for SlashingScenario<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let misbehaving = env
+                .node_clients
+                .get(self.misbehaving_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.misbehaving_idx))?;
+            if env.node_clients.len() < 2 {
+                return Err(eyre::eyre!("Slashing scenario requires at least one honest node"));
+            }
+            if self.invalid_blocks == 0 {
+                return Err(eyre::eyre!("Slashing scenario requires at least one invalid block"));
+            }
+            let misbehaving_id = node_record(misbehaving).await?.id;
+
+            let LatestPayloadInputs {
+                block,
+                block_hash,
+                versioned_hashes,
+                parent_beacon_block_root,
+            } = LatestPayloadInputs::fetch(&misbehaving.rpc).await?;
+
+            for _ in 0..self.invalid_blocks {
+                let mut invalid_block = block.clone();
+                PayloadMutation::StateRoot.apply(&mut invalid_block.header, block_hash);
+                let invalid_payload = ExecutionPayloadV3::from_block_slow(&invalid_block);
+                let invalid_hash = invalid_payload.payload_inner.payload_inner.block_hash;
+
+                for (idx, client) in env.node_clients.iter().enumerate() {
+                    if idx == self.misbehaving_idx {
+                        continue
+                    }
+                    let peers = client
+                        .peers
+                        .as_ref()
+                        .ok_or_else(|| eyre::eyre!("Node {idx} has no peer manager handle"))?;
+
+                    let status = EngineApiClient::<Engine>::new_payload_v3(
+                        env.engine_client(idx).await?,
+                        invalid_payload.clone(),
+                        versioned_hashes.clone(),
+                        parent_beacon_block_root,
+                    )
+                    .await?
+                    .status;
+                    if !status.is_invalid() {
+                        return Err(eyre::eyre!(
+                            "Node {idx} did not reject invalid block {invalid_hash}: {status:?}"
+                        ));
+                    }
+                    peers.reputation_change(misbehaving_id, ReputationChangeKind::BadBlock);
+
+                    let head =
+                        EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                            &client.rpc,
+                            BlockNumberOrTag::Latest,
+                        )
+                        .await?
+                        .ok_or_else(|| eyre::eyre!("Node {idx}: No latest header found"))?;
+                    if head.hash != block_hash {
+                        return Err(eyre::eyre!(
+                            "Node {idx} head {} ({}) left the valid branch at {block_hash}",
+                            head.inner.number,
+                            head.hash
+                        ));
+                    }
+                }
+                debug!(
+                    "Node {} ({misbehaving_id}) proposed invalid block {invalid_hash}",
+                    self.misbehaving_idx
+                );
+            }
+
+            let deadline = Instant::now() + self.disconnect_timeout;
+            for (idx, client) in env.node_clients.iter().enumerate() {
+                if idx == self.misbehaving_idx {
+                    continue
+                }
+                let peers = client
+                    .peers
+                    .as_ref()
+                    .ok_or_else(|| eyre::eyre!("Node {idx} has no peer manager handle"))?;
+
+                loop {
+                    let connected = AdminApiClient::peers(&client.rpc).await?.iter().any(|p| {
+                        p.enode.parse::<NodeRecord>().is_ok_and(|r| r.id == misbehaving_id)
+                    });
+                    let peer = peers.peer_by_id(misbehaving_id).await;
+                    let banned = peer.as_ref().is_none_or(|peer| peer.is_banned());
+                    if !connected && banned {
+                        break
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(eyre::eyre!(
+                            "Node {idx} did not drop misbehaving node {} within {:?} (connected: \
+                             {connected}, reputation: {:?})",
+                            self.misbehaving_idx,
+                            self.disconnect_timeout,
+                            peer.map(|peer| peer.reputation)
+                        ));
+                    }
+                    sleep(Duration::from_millis(100)).await;
+                }
+            }
+
+            debug!(
+                "Node {} ({misbehaving_id}) was banned after proposing {} invalid blocks",
+                self.misbehaving_idx, self.invalid_blocks
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that fetches a contiguous range of blocks from a node and stores them in
+/// `env.block_ranges` under a label.
+///
+/// Blocks are fetched concurrently in chunks of `chunk_size` requests. If the range extends past
+/// the node's head, only the blocks up to the head are fetched and the stored range is marked as
+/// truncated.
+#[derive(Debug)]
+pub struct CollectBlockRange {
+    /// The node index to fetch the blocks from
+    pub node_idx: usize,
+    /// Label to store the blocks under
+    pub label: String,
+    /// First block number of the range
+    pub start: u64,
+    /// Last block number of the range, inclusive
+    pub end: u64,
+    /// Maximum number of concurrent requests
+    pub chunk_size: usize,
+}
+
+impl CollectBlockRange {
+    /// Create a new `CollectBlockRange` action fetching blocks `start..=end` from node 0
+    pub fn new(label: impl Into<String>, start: u64, end: u64) -> Self {
+        Self { node_idx: 0, label: label.into(), start, end, chunk_size: 16 }
+    }
+
+    /// Set the node index to fetch the blocks from
+    pub const fn with_node_idx(mut self, node_idx: usize) -> Self {
+        self.node_idx = node_idx;
+        self
+    }
+
+    /// Set the maximum number of concurrent requests
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+impl<I> Action<I> for CollectBlockRange
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.start > self.end {
+                return Err(eyre::eyre!("Invalid block range {}..={}", self.start, self.end));
+            }
+            if self.chunk_size == 0 {
+                return Err(eyre::eyre!("Chunk size must be greater than zero"));
+            }
+
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let head = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                &node_client.rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found from rpc"))?
+            .inner
+            .number;
+
+            let truncated = self.end > head;
+            if truncated {
+                debug!(
+                    "Block range {}..={} extends past head {head} of node {}, truncating",
+                    self.start, self.end, self.node_idx
+                );
+            }
+
+            let numbers = (self.start..=self.end.min(head)).collect::<Vec<_>>();
+            let mut blocks = Vec::with_capacity(numbers.len());
+            for chunk in numbers.chunks(self.chunk_size) {
+                let fetched = try_join_all(chunk.iter().map(|&number| async move {
+                    EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                        &node_client.rpc,
+                        number.into(),
+                        false,
+                    )
+                    .await?
+                    .ok_or_else(|| eyre::eyre!("Block {number} not found"))
+                }))
+                .await?;
+                blocks.extend(fetched);
+            }
+
+            debug!(
+                "Collected {} blocks from node {} under label '{}'",
+                blocks.len(),
+                self.node_idx,
+                self.label
+            );
+            env.block_ranges.insert(
+                self.label.clone(),
+                CollectedBlockRange { blocks, requested_end: self.end, truncated },
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that fetches the next payload through several `engine_getPayload` versions and asserts
+/// they describe the same block.
+///
+/// The payload is requested with `engine_forkchoiceUpdatedV3` if the payload attributes carry a
+/// parent beacon block root, i.e. from Cancun on, and with `engine_forkchoiceUpdatedV2` before.
+/// Since a payload job may be dropped once it is resolved, the payload is requested again with the
+/// same attributes before every fetch, which must yield the same payload id. The block hash, state
+/// root and transactions must be identical across all versions, and the blobs bundle and the
+/// execution requests must match between the versions that carry them. The transaction pool must
+/// not change while the action runs, otherwise the rebuilt payloads may legitimately differ.
+///
+/// Every version is only served for some forks, and carries the blobs and requests of that fork:
+///  - `V2` is only served before Cancun and carries neither, the payload must not reference blobs
+///  - `V3` is served from Cancun on and carries a blobs bundle, which must hold one commitment for
+///    every blob referenced by the payload
+///  - `V4` is only served from Prague on and carries the execution requests in addition to the
+///    blobs bundle
+///
+/// So only Prague payloads can be compared, with the default versions `V3` and `V4`. The
+/// testsuite genesis is Cancun, the chain spec must activate Prague for this action.
+#[derive(Debug)]
+pub struct AssertSameBlockAcrossVersions<Engine> {
+    /// The node index building the payload
+    pub node_idx: usize,
+    /// The `engine_getPayload` versions to compare
+    pub versions: Vec<EngineApiMessageVersion>,
+    /// Time given to the node to build the payload before each fetch
+    pub build_time: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertSameBlockAcrossVersions<Engine> {
+    /// Create a new `AssertSameBlockAcrossVersions` action comparing the given versions
+    pub fn new(node_idx: usize, versions: Vec<EngineApiMessageVersion>) -> Self {
+        Self {
+            node_idx,
+            versions,
+            build_time: Duration::from_secs(1),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the time given to the node to build the payload before each fetch
+    pub const fn with_build_time(mut self, build_time: Duration) -> Self {
+        self.build_time = build_time;
+        self
+    }
+}
+
+impl<Engine> Default for AssertSameBlockAcrossVersions<Engine> {
+    fn default() -> Self {
+        Self::new(0, vec![EngineApiMessageVersion::V3, EngineApiMessageVersion::V4])
+    }
+}
+
+/// A payload fetched by [`AssertSameBlockAcrossVersions`] with one `engine_getPayload` version.
+#[derive(Debug)]
+struct VersionedPayload {
+    /// The `engine_getPayload` version
+    version: EngineApiMessageVersion,
+    /// The execution payload
+    payload: ExecutionPayloadV1,
+    /// The blobs bundle, from V3 on
+    blobs_bundle: Option<BlobsBundleV1>,
+    /// The execution requests, from V4 on
+    execution_requests: Option<Requests>,
+}
+
+impl<Engine> Action<Engine> for AssertSameBlockAcrossVersions<Engine>
+where
+    Engine: EngineTypes<
+            ExecutionPayloadEnvelopeV2 = ExecutionPayloadEnvelopeV2,
+            ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3,
+            ExecutionPayloadEnvelopeV4 = ExecutionPayloadEnvelopeV4,
+        > + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.versions.len() < 2 {
+                return Err(eyre::eyre!("At least two getPayload versions are required"));
+            }
+
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let is_cancun = payload_attributes.parent_beacon_block_root.is_some();
+            for &version in &self.versions {
+                match version {
+                    EngineApiMessageVersion::V2 if is_cancun => {
+                        return Err(eyre::eyre!(
+                            "getPayloadV2 is only served before Cancun, but the payload attributes \
+                             carry a parent beacon block root"
+                        ))
+                    }
+                    EngineApiMessageVersion::V3 | EngineApiMessageVersion::V4 if !is_cancun => {
+                        return Err(eyre::eyre!(
+                            "getPayload{version:?} is only served from Cancun on, but the payload \
+                             attributes carry no parent beacon block root"
+                        ))
+                    }
+                    EngineApiMessageVersion::V2 |
+                    EngineApiMessageVersion::V3 |
+                    EngineApiMessageVersion::V4 => {}
+                    version => {
+                        return Err(eyre::eyre!("Unsupported getPayload version {version:?}"))
+                    }
+                }
+            }
+
+            let mut payload_id = None;
+            let mut fetched: Vec<VersionedPayload> = Vec::with_capacity(self.versions.len());
+            for &version in &self.versions {
+                let fcu_result = if is_cancun {
+                    EngineApiClient::<Engine>::fork_choice_updated_v3(
+                        env.engine_client(self.node_idx).await?,
+                        fork_choice_state,
+                        Some(payload_attributes.clone()),
+                    )
+                    .await?
+                } else {
+                    EngineApiClient::<Engine>::fork_choice_updated_v2(
+                        env.engine_client(self.node_idx).await?,
+                        fork_choice_state,
+                        Some(payload_attributes.clone()),
+                    )
+                    .await?
+                };
+                let id = fcu_result
+                    .payload_id
+                    .ok_or_else(|| eyre::eyre!("No payload ID returned from forkChoiceUpdated"))?;
+                if *payload_id.get_or_insert(id) != id {
+                    return Err(eyre::eyre!(
+                        "Same payload attributes yielded payload ID {id}, expected {payload_id:?}"
+                    ));
+                }
+
+                sleep(self.build_time).await;
+
+                let fetched_payload = match version {
+                    EngineApiMessageVersion::V2 => {
+                        let envelope = EngineApiClient::<Engine>::get_payload_v2(
+                            env.engine_client(self.node_idx).await?,
+                            id,
+                        )
+                        .await
+                        .map_err(|err| engine_error(err, self.node_idx, "engine_getPayloadV2"))?;
+                        let payload = match envelope.execution_payload {
+                            ExecutionPayloadFieldV2::V1(payload) => payload,
+                            ExecutionPayloadFieldV2::V2(payload) => payload.payload_inner,
+                        };
+                        VersionedPayload {
+                            version,
+                            payload,
+                            blobs_bundle: None,
+                            execution_requests: None,
+                        }
+                    }
+                    EngineApiMessageVersion::V3 => {
+                        let envelope = EngineApiClient::<Engine>::get_payload_v3(
+                            env.engine_client(self.node_idx).await?,
+                            id,
+                        )
+                        .await
+                        .map_err(|err| engine_error(err, self.node_idx, "engine_getPayloadV3"))?;
+                        VersionedPayload {
+                            version,
+                            payload: envelope.execution_payload.payload_inner.payload_inner,
+                            blobs_bundle: Some(envelope.blobs_bundle),
+                            execution_requests: None,
+                        }
+                    }
+                    _ => {
+                        // Prague is not known from the payload attributes, a pre-Prague payload
+                        // fails with an unsupported fork error
+                        let envelope = EngineApiClient::<Engine>::get_payload_v4(
+                            env.engine_client(self.node_idx).await?,
+                            id,
+                        )
+                        .await
+                        .map_err(|err| engine_error(err, self.node_idx, "engine_getPayloadV4"))?;
+                        VersionedPayload {
+                            version,
+                            payload: envelope
+                                .envelope_inner
+                                .execution_payload
+                                .payload_inner
+                                .payload_inner,
+                            blobs_bundle: Some(envelope.envelope_inner.blobs_bundle),
+                            execution_requests: Some(envelope.execution_requests),
+                        }
+                    }
+                };
+
+                let mut referenced_blobs = 0;
+                for raw in &fetched_payload.payload.transactions {
+                    let tx = TxEnvelope::decode_2718(&mut raw.as_ref())?;
+                    referenced_blobs += tx.blob_versioned_hashes().map_or(0, |hashes| hashes.len());
+                }
+                match &fetched_payload.blobs_bundle {
+                    None if referenced_blobs > 0 => {
+                        return Err(eyre::eyre!(
+                            "getPayload{version:?} carries no blobs bundle, but the payload \
+                             references {referenced_blobs} blobs"
+                        ))
+                    }
+                    Some(bundle)
+                        if bundle.commitments.len() != referenced_blobs ||
+                            bundle.blobs.len() != referenced_blobs =>
+                    {
+                        return Err(eyre::eyre!(
+                            "getPayload{version:?} blobs bundle has {} blobs and {} commitments, \
+                             but the payload references {referenced_blobs} blobs",
+                            bundle.blobs.len(),
+                            bundle.commitments.len()
+                        ))
+                    }
+                    _ => {}
+                }
+                fetched.push(fetched_payload);
+            }
+
+            let base = &fetched[0];
+            for fetched_payload in &fetched[1..] {
+                let (base_version, version) = (base.version, fetched_payload.version);
+                let (base_payload, payload) = (&base.payload, &fetched_payload.payload);
+                if payload.block_hash != base_payload.block_hash {
+                    return Err(eyre::eyre!(
+                        "Block hash mismatch: {base_version:?} {}, {version:?} {}",
+                        base_payload.block_hash,
+                        payload.block_hash
+                    ));
+                }
+                if payload.state_root != base_payload.state_root {
+                    return Err(eyre::eyre!(
+                        "State root mismatch: {base_version:?} {}, {version:?} {}",
+                        base_payload.state_root,
+                        payload.state_root
+                    ));
+                }
+                if payload.transactions != base_payload.transactions {
+                    return Err(eyre::eyre!(
+                        "Transactions mismatch: {base_version:?} has {}, {version:?} has {}",
+                        base_payload.transactions.len(),
+                        payload.transactions.len()
+                    ));
+                }
+                if let (Some(base_blobs), Some(blobs)) =
+                    (&base.blobs_bundle, &fetched_payload.blobs_bundle)
+                {
+                    if blobs != base_blobs {
+                        return Err(eyre::eyre!(
+                            "Blobs bundle mismatch between {base_version:?} and {version:?}"
+                        ));
+                    }
+                }
+                if let (Some(base_requests), Some(requests)) =
+                    (&base.execution_requests, &fetched_payload.execution_requests)
+                {
+                    if requests != base_requests {
+                        return Err(eyre::eyre!(
+                            "Execution requests mismatch between {base_version:?} and {version:?}"
+                        ));
+                    }
+                }
+            }
+
+            debug!(
+                "getPayload versions {:?} agree on block {}",
+                self.versions, base.payload.block_hash
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that relaunches all nodes on fresh datadirs and resets the chain tracking state of the
+/// environment.
+///
+/// This gives independent test cases a clean slate without rebuilding the whole setup. The nodes
+/// are reconnected in a chain, closing the ring for more than two nodes like the setup does, and
+/// every node must report the genesis block as its head.
+#[derive(Debug)]
+pub struct RestartClusterFromGenesis {
+    /// Maximum time to wait for each node to shut down gracefully
+    pub shutdown_timeout: Duration,
+    /// Maximum time to wait for the nodes to reconnect
+    pub connect_timeout: Duration,
+}
+
+impl RestartClusterFromGenesis {
+    /// Create a new `RestartClusterFromGenesis` action
+    pub const fn new() -> Self {
+        Self { shutdown_timeout: Duration::from_secs(10), connect_timeout: Duration::from_secs(10) }
+    }
+
+    /// Set the maximum time to wait for each node to shut down gracefully
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for the nodes to reconnect
+    pub const fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+}
+
+impl Default for RestartClusterFromGenesis {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Action<I> for RestartClusterFromGenesis
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let launcher = env
+                .node_launcher
+                .clone()
+                .ok_or_else(|| eyre::eyre!("No node launcher available, was the setup applied?"))?;
+
+            for (idx, client) in env.node_clients.iter_mut().enumerate() {
+                let Some(lifecycle) = client.lifecycle.as_mut() else {
+                    return Err(eyre::eyre!("Node {idx} was not launched by the setup"));
+                };
+                if lifecycle.is_running() && !lifecycle.shutdown(self.shutdown_timeout).await? {
+                    debug!("Node {idx} did not shut down within {:?}", self.shutdown_timeout);
+                }
+            }
+
+            let node_count = env.node_clients.len();
+            let mut clients = Vec::with_capacity(node_count);
+            for idx in 0..node_count {
+                let client = launcher.launch(idx, None).await?;
+
+                let head = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                    &client.rpc,
+                    BlockNumberOrTag::Latest,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("Node {idx}: No latest header found"))?;
+                if head.inner.number != 0 {
+                    return Err(eyre::eyre!(
+                        "Node {idx} restarted at block {} instead of genesis",
+                        head.inner.number
+                    ));
+                }
+
+                clients.push(client);
+            }
+
+            for pair in clients.windows(2) {
+                connect_nodes(&pair[0], &pair[1], self.connect_timeout).await?;
+            }
+            if node_count > 2 {
+                connect_nodes(&clients[node_count - 1], &clients[0], self.connect_timeout).await?;
+            }
+
+            env.node_clients = clients;
+            env.reset_chain_tracking();
+
+            debug!("Restarted {node_count} nodes from genesis");
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts an included blob transaction pays at least the blob base fee of its block.
+///
+/// The blob base fee is derived from the `excess_blob_gas` of the block header using the given
+/// blob parameters, which default to Cancun.
+#[derive(Debug)]
+pub struct AssertMaxFeePerBlobGas {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the blob transaction
+    pub tx_hash: B256,
+    /// Blob parameters of the block's fork
+    pub blob_params: BlobParams,
+}
+
+impl AssertMaxFeePerBlobGas {
+    /// Create a new `AssertMaxFeePerBlobGas` action
+    pub const fn new(node_idx: usize, tx_hash: B256) -> Self {
+        Self { node_idx, tx_hash, blob_params: BlobParams::cancun() }
+    }
+
+    /// Set the blob parameters of the block's fork
+    pub const fn with_blob_params(mut self, blob_params: BlobParams) -> Self {
+        self.blob_params = blob_params;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertMaxFeePerBlobGas
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let tx = EthApiClient::<Transaction, Block, Receipt, Header>::transaction_by_hash(
+                &node_client.rpc,
+                self.tx_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Transaction {} not found", self.tx_hash))?;
+            let max_fee_per_blob_gas = tx.max_fee_per_blob_gas().ok_or_else(|| {
+                eyre::eyre!("Transaction {} is not a blob transaction", self.tx_hash)
+            })?;
+            let block_hash = tx
+                .block_hash
+                .ok_or_else(|| eyre::eyre!("Transaction {} is not included", self.tx_hash))?;
+
+            let header = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(
+                &node_client.rpc,
+                block_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No header found for block {block_hash}"))?;
+            let excess_blob_gas = header
+                .inner
+                .excess_blob_gas
+                .ok_or_else(|| eyre::eyre!("Block {block_hash} has no excess blob gas"))?;
+            let blob_base_fee = self.blob_params.calc_blob_fee(excess_blob_gas);
+
+            if max_fee_per_blob_gas < blob_base_fee {
+                return Err(eyre::eyre!(
+                    "Transaction {} max fee per blob gas {max_fee_per_blob_gas} is below blob base fee {blob_base_fee} of block {block_hash}",
+                    self.tx_hash
+                ));
+            }
+
+            debug!(
+                "Transaction {} max fee per blob gas {max_fee_per_blob_gas} covers blob base fee {blob_base_fee}",
+                self.tx_hash
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that randomly isolates and heals nodes while producing blocks, then asserts the cluster
+/// converges to a single head.
+///
+/// Every step either isolates a random node from all its peers or reconnects an isolated node to
+/// all connected nodes, then produces a block on a random connected node and waits for a random
+/// interval. At least one node always stays connected. Blocks are only sent to the connected
+/// nodes; once healed, a node downloads the blocks it missed from its peers when a forkchoice
+/// update points it at the head. After `duration` all nodes are healed and must agree on the head
+/// within `convergence_timeout`. All random choices are derived from `seed`, so a failing run can
+/// be reproduced.
+#[derive(Debug)]
+pub struct ChaosMonkey<Engine> {
+    /// Seed of the random choices
+    pub seed: u64,
+    /// How long to keep toggling node isolation
+    pub duration: Duration,
+    /// Maximum time to wait between two steps
+    pub max_interval: Duration,
+    /// Maximum time to wait for a node to connect or disconnect
+    pub peer_timeout: Duration,
+    /// Maximum time to wait for all nodes to agree on the head once healed
+    pub convergence_timeout: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ChaosMonkey<Engine> {
+    /// Create a new `ChaosMonkey` action
+    pub fn new(seed: u64, duration: Duration) -> Self {
+        Self {
+            seed,
+            duration,
+            max_interval: Duration::from_secs(1),
+            peer_timeout: Duration::from_secs(10),
+            convergence_timeout: Duration::from_secs(30),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the maximum time to wait between two steps
+    pub const fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Set the maximum time to wait for a node to connect or disconnect
+    pub const fn with_peer_timeout(mut self, peer_timeout: Duration) -> Self {
+        self.peer_timeout = peer_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for all nodes to agree on the head once healed
+    pub const fn with_convergence_timeout(mut self, convergence_timeout: Duration) -> Self {
+        self.convergence_timeout = convergence_timeout;
+        self
+    }
+
+    /// Reconnects the isolated node to all connected nodes and waits until it downloaded the
+    /// blocks it missed from them.
+    async fn heal(
+        &self,
+        env: &Environment<Engine>,
+        isolated: &mut HashSet<usize>,
+        node_idx: usize,
+    ) -> Result<()>
+    where
+        Engine: EngineTypes,
+    {
+        isolated.remove(&node_idx);
+        for (peer_idx, peer) in env.node_clients.iter().enumerate() {
+            if peer_idx == node_idx || isolated.contains(&peer_idx) {
+                continue
+            }
+            connect_nodes(&env.node_clients[node_idx], peer, self.peer_timeout).await.map_err(
+                |e| eyre::eyre!("Failed to reconnect node {node_idx} to node {peer_idx}: {e}"),
+            )?;
+        }
+
+        // the node answers `SYNCING` to the forkchoice update until it downloaded the missing
+        // blocks from its peers
+        let Some(head) = env.latest_block_info.as_ref().map(|block| block.hash) else {
+            return Ok(())
+        };
+        let fork_choice_state = ForkchoiceState {
+            head_block_hash: head,
+            safe_block_hash: head,
+            finalized_block_hash: head,
+        };
+        let deadline = Instant::now() + self.convergence_timeout;
+        loop {
+            let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(node_idx).await?,
+                fork_choice_state,
+                None,
+            )
+            .await?
+            .payload_status
+            .status;
+            if status.is_valid() {
+                return Ok(())
+            }
+            if Instant::now() >= deadline {
+                return Err(eyre::eyre!(
+                    "Node {node_idx} did not catch up with head {head} within {:?} after healing: \
+                     {status:?}",
+                    self.convergence_timeout
+                ));
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+impl<Engine> Action<Engine> for ChaosMonkey<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_count = env.node_clients.len();
+            if node_count < 2 {
+                return Err(eyre::eyre!("Chaos monkey requires at least two nodes"));
+            }
+
+            info!("Running chaos monkey with seed {} for {:?}", self.seed, self.duration);
+            let mut rng = StdRng::seed_from_u64(self.seed);
+            let mut isolated = HashSet::new();
+            let deadline = Instant::now() + self.duration;
+
+            while Instant::now() < deadline {
+                let node_idx = rng.random_range(0..node_count);
+                if isolated.contains(&node_idx) {
+                    debug!("Chaos monkey healing node {node_idx}");
+                    self.heal(env, &mut isolated, node_idx).await?;
+                } else if isolated.len() + 1 < node_count {
+                    debug!("Chaos monkey isolating node {node_idx}");
+                    for (peer_idx, peer) in env.node_clients.iter().enumerate() {
+                        if peer_idx == node_idx || isolated.contains(&peer_idx) {
+                            continue
+                        }
+                        disconnect_nodes(peer, &env.node_clients[node_idx], self.peer_timeout)
+                            .await
+                            .map_err(|e| {
+                                eyre::eyre!(
+                                    "Failed to disconnect node {node_idx} from node {peer_idx}: {e}"
+                                )
+                            })?;
+                    }
+                    isolated.insert(node_idx);
+                }
+
+                let connected =
+                    (0..node_count).filter(|idx| !isolated.contains(idx)).collect::<Vec<_>>();
+                let producer_idx = connected[rng.random_range(0..connected.len())];
+                ProduceBlockWithSpecificProducer::<Engine>::new(producer_idx)
+                    .with_targets(connected)
+                    .execute(env)
+                    .await?;
+
+                let interval = rng.random_range(0..=self.max_interval.as_millis() as u64);
+                sleep(Duration::from_millis(interval)).await;
+            }
+
+            let mut remaining = isolated.iter().copied().collect::<Vec<_>>();
+            remaining.sort_unstable();
+            for node_idx in remaining {
+                debug!("Chaos monkey healing node {node_idx}");
+                self.heal(env, &mut isolated, node_idx).await?;
+            }
+
+            let convergence_deadline = Instant::now() + self.convergence_timeout;
+            loop {
+                let heads = try_join_all(env.node_clients.iter().map(|client| async {
+                    EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                        &client.rpc,
+                        BlockNumberOrTag::Latest,
+                    )
+                    .await?
+                    .map(|header| header.hash)
+                    .ok_or_else(|| eyre::eyre!("No latest header found"))
+                }))
+                .await?;
+
+                if heads.iter().all(|head| *head == heads[0]) {
+                    debug!("Cluster converged to head {} after chaos", heads[0]);
+                    return Ok(())
+                }
+                if Instant::now() >= convergence_deadline {
+                    return Err(eyre::eyre!(
+                        "Nodes did not converge within {:?} after chaos with seed {}: {heads:?}",
+                        self.convergence_timeout,
+                        self.seed
+                    ));
+                }
+                sleep(Duration::from_millis(100)).await;
+            }
+        })
+    }
+}
+
+/// Action that asserts the balances of prefunded accounts at the genesis block.
+///
+/// All mismatching balances are collected and reported together.
+#[derive(Debug)]
+pub struct GenesisAccountFundCheck {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Expected genesis balance of each account
+    pub expected: BTreeMap<Address, U256>,
+}
+
+impl GenesisAccountFundCheck {
+    /// Create a new `GenesisAccountFundCheck` action
+    pub fn new(node_idx: usize, expected: impl IntoIterator<Item = (Address, U256)>) -> Self {
+        Self { node_idx, expected: expected.into_iter().collect() }
+    }
+}
+
+impl<I> Action<I> for GenesisAccountFundCheck
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let mut mismatches = Vec::new();
+            for (address, expected) in &self.expected {
+                let balance = EthApiClient::<Transaction, Block, Receipt, Header>::balance(
+                    &node_client.rpc,
+                    *address,
+                    Some(BlockId::Number(BlockNumberOrTag::Number(0))),
+                )
+                .await?;
+                if balance != *expected {
+                    mismatches.push(format!("{address}: expected {expected}, got {balance}"));
+                }
+            }
+
+            if !mismatches.is_empty() {
+                return Err(eyre::eyre!(
+                    "{} of {} genesis balances mismatch: {}",
+                    mismatches.len(),
+                    self.expected.len(),
+                    mismatches.join(", ")
+                ));
+            }
+
+            debug!("All {} genesis balances match", self.expected.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that sends the same forkchoice update with payload attributes twice and asserts both
+/// calls return the same payload id.
+///
+/// The forkchoice state and attributes are the ones [`GenerateNextPayload`] would use for the next
+/// block.
+#[derive(Debug)]
+pub struct AssertPayloadIdStable<Engine> {
+    /// The node index to send the forkchoice updates to
+    pub node_idx: usize,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertPayloadIdStable<Engine> {
+    /// Create a new `AssertPayloadIdStable` action
+    pub fn new(node_idx: usize) -> Self {
+        Self { node_idx, _phantom: Default::default() }
+    }
+}
+
+impl<Engine> Default for AssertPayloadIdStable<Engine> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<Engine> Action<Engine> for AssertPayloadIdStable<Engine>
+where
+    Engine: EngineTypes + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let mut payload_ids = Vec::with_capacity(2);
+            for _ in 0..2 {
+                let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                    env.engine_client(self.node_idx).await?,
+                    fork_choice_state,
+                    Some(payload_attributes.clone()),
+                )
+                .await?;
+                let payload_id = fcu_result
+                    .payload_id
+                    .ok_or_else(|| eyre::eyre!("No payload ID returned from forkChoiceUpdated"))?;
+                payload_ids.push(payload_id);
+            }
+
+            if payload_ids[0] != payload_ids[1] {
+                return Err(eyre::eyre!(
+                    "Identical forkchoice updates returned different payload IDs: {} and {}",
+                    payload_ids[0],
+                    payload_ids[1]
+                ));
+            }
+
+            debug!("Identical forkchoice updates returned payload ID {}", payload_ids[0]);
+            Ok(())
+        })
+    }
+}
+
+/// Action that runs an inner action and, if it fails, attaches the tail of a node's log file to
+/// the error.
+///
+/// The log file defaults to the node's own log file, which nodes launched by the testsuite only
+/// write if the test installed a [`NodeLogLayer`](crate::testsuite::setup::NodeLogLayer).
+/// [`DumpNodeLogsOnFailure::with_log_path`] can point to a different file. Failing to read the logs
+/// never hides the original error.
+#[expect(missing_debug_implementations)]
+pub struct DumpNodeLogsOnFailure<I> {
+    /// The node index whose logs are attached
+    pub node_idx: usize,
+    /// Number of trailing log lines to attach
+    pub tail_lines: usize,
+    /// Log file to read, defaults to the node's own log file
+    pub log_path: Option<PathBuf>,
+    /// Action to run
+    pub inner: Box<dyn Action<I>>,
+}
+
+impl<I> DumpNodeLogsOnFailure<I> {
+    /// Create a new `DumpNodeLogsOnFailure` action
+    pub fn new<A: Action<I>>(node_idx: usize, inner: A) -> Self {
+        Self { node_idx, tail_lines: 50, log_path: None, inner: Box::new(inner) }
+    }
+
+    /// Set the number of trailing log lines to attach
+    pub const fn with_tail_lines(mut self, tail_lines: usize) -> Self {
+        self.tail_lines = tail_lines;
+        self
+    }
+
+    /// Set the log file to read
+    pub fn with_log_path(mut self, log_path: impl Into<PathBuf>) -> Self {
+        self.log_path = Some(log_path.into());
+        self
+    }
+}
+
+impl<I> Action<I> for DumpNodeLogsOnFailure<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let Err(err) = self.inner.execute(env).await else { return Ok(()) };
+
+            let log_path = self
+                .log_path
+                .clone()
+                .or_else(|| env.node_clients.get(self.node_idx)?.log_path.clone());
+            let Some(log_path) = log_path else {
+                debug!("No log file found for node {}", self.node_idx);
+                return Err(err)
+            };
+
+            match tokio::fs::read_to_string(&log_path).await {
+                Ok(logs) => {
+                    let lines = logs.lines().collect::<Vec<_>>();
+                    let tail = lines[lines.len().saturating_sub(self.tail_lines)..].join("\n");
+                    Err(err.wrap_err(format!(
+                        "Last {} log lines of node {} ({}):\n{tail}",
+                        self.tail_lines.min(lines.len()),
+                        self.node_idx,
+                        log_path.display()
+                    )))
+                }
+                Err(read_err) => {
+                    debug!("Failed to read log file {}: {read_err}", log_path.display());
+                    Err(err)
+                }
+            }
+        })
+    }
+}
+
+/// Action that asserts the blocks of a range were produced evenly by all nodes.
+///
+/// Each node must have produced the expected share of `range_len / node_count` blocks, within
+/// `tolerance` blocks. Producers are taken from [`Environment::block_producer`], every block of
+/// the range must have a recorded producer.
+#[derive(Debug)]
+pub struct AssertBlockRangeProducedByDistinctNodes {
+    /// First block number of the range
+    pub start: u64,
+    /// Last block number of the range, inclusive
+    pub end: u64,
+    /// Maximum number of blocks a node's production may deviate from the expected share
+    pub tolerance: u64,
+}
+
+impl AssertBlockRangeProducedByDistinctNodes {
+    /// Create a new `AssertBlockRangeProducedByDistinctNodes` action
+    pub const fn new(start: u64, end: u64) -> Self {
+        Self { start, end, tolerance: 1 }
+    }
+
+    /// Set the maximum number of blocks a node's production may deviate from the expected share
+    pub const fn with_tolerance(mut self, tolerance: u64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertBlockRangeProducedByDistinctNodes
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.start > self.end {
+                return Err(eyre::eyre!("Invalid block range {}..={}", self.start, self.end));
+            }
+            let node_count = env.node_clients.len();
+            if node_count == 0 {
+                return Err(eyre::eyre!("No node clients available"));
+            }
+
+            let mut distribution = vec![0u64; node_count];
+            let mut unknown = Vec::new();
+            for number in self.start..=self.end {
+                match env.block_producer(number) {
+                    Some(idx) if idx < node_count => distribution[idx] += 1,
+                    _ => unknown.push(number),
+                }
+            }
+            if !unknown.is_empty() {
+                return Err(eyre::eyre!("No known producer for blocks {unknown:?}"));
+            }
+
+            let expected = (self.end - self.start + 1) as f64 / node_count as f64;
+            let unfair = distribution
+                .iter()
+                .enumerate()
+                .filter(|(_, produced)| {
+                    (**produced as f64 - expected).abs() > self.tolerance as f64
+                })
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            if !unfair.is_empty() {
+                return Err(eyre::eyre!(
+                    "Nodes {unfair:?} deviate from the expected share of {expected:.2} blocks by more than {}: {distribution:?}",
+                    self.tolerance
+                ));
+            }
+
+            debug!(
+                "Blocks {}..={} produced with distribution {distribution:?}",
+                self.start, self.end
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that resolves a built payload with `engine_getPayloadV3` and asserts its blobs bundle is
+/// consistent with the payload.
+///
+/// The bundle must hold the same number of blobs, commitments and proofs, and the versioned hash
+/// of each commitment must match the blob versioned hashes of the payload's transactions, in
+/// order. The payload must not have been resolved before.
+#[derive(Debug)]
+pub struct AssertPayloadBlobsBundle<Engine> {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The payload to check, defaults to `env.next_payload_id`
+    pub payload_id: Option<PayloadId>,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertPayloadBlobsBundle<Engine> {
+    /// Create a new `AssertPayloadBlobsBundle` action for the next payload
+    pub fn new(node_idx: usize) -> Self {
+        Self { node_idx, payload_id: None, _phantom: Default::default() }
+    }
+
+    /// Set the payload to check
+    pub const fn with_payload_id(mut self, payload_id: PayloadId) -> Self {
+        self.payload_id = Some(payload_id);
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for AssertPayloadBlobsBundle<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let payload_id = self
+                .payload_id
+                .or(env.next_payload_id)
+                .ok_or_else(|| eyre::eyre!("No payload ID available"))?;
+
+            let envelope = EngineApiClient::<Engine>::get_payload_v3(
+                env.engine_client(self.node_idx).await?,
+                payload_id,
+            )
+            .await?;
+            let bundle = envelope.blobs_bundle;
+            if bundle.blobs.len() != bundle.commitments.len() ||
+                bundle.commitments.len() != bundle.proofs.len()
+            {
+                return Err(eyre::eyre!(
+                    "Payload {payload_id} blobs bundle has {} blobs, {} commitments and {} proofs",
+                    bundle.blobs.len(),
+                    bundle.commitments.len(),
+                    bundle.proofs.len()
+                ));
+            }
+
+            let mut versioned_hashes = Vec::new();
+            for raw in &envelope.execution_payload.payload_inner.payload_inner.transactions {
+                let tx = TxEnvelope::decode_2718(&mut raw.as_ref())?;
+                if let Some(hashes) = tx.blob_versioned_hashes() {
+                    versioned_hashes.extend_from_slice(hashes);
+                }
+            }
+            if versioned_hashes.len() != bundle.commitments.len() {
+                return Err(eyre::eyre!(
+                    "Payload {payload_id} transactions reference {} blobs, bundle has {} commitments",
+                    versioned_hashes.len(),
+                    bundle.commitments.len()
+                ));
+            }
+
+            for (idx, (commitment, expected)) in
+                bundle.commitments.iter().zip(&versioned_hashes).enumerate()
+            {
+                let versioned_hash = kzg_to_versioned_hash(commitment.as_slice());
+                if versioned_hash != *expected {
+                    return Err(eyre::eyre!(
+                        "Payload {payload_id} commitment {idx} hashes to {versioned_hash}, transaction expects {expected}"
+                    ));
+                }
+            }
+
+            debug!(
+                "Payload {payload_id} blobs bundle with {} blobs is consistent",
+                bundle.blobs.len()
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that requests a payload with a timestamp far ahead of its parent, as a proposer with a
+/// skewed clock would, and asserts whether the node accepts the payload attributes.
+///
+/// The attributes are accepted if the forkchoice update returns a payload id, and rejected if it
+/// fails with the invalid payload attributes error (code `-38003`). The built payload is not
+/// imported, so the chain tracking state of the environment is left untouched.
+#[derive(Debug)]
+pub struct SimulateClockSkew<Engine> {
+    /// The node index to send the payload attributes to
+    pub node_idx: usize,
+    /// How far ahead of the parent the timestamp is
+    pub skew: Duration,
+    /// Whether the node is expected to accept the payload attributes
+    pub expect_accepted: bool,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> SimulateClockSkew<Engine> {
+    /// Create a new `SimulateClockSkew` action expecting the node to accept the attributes
+    pub fn new(node_idx: usize, skew: Duration) -> Self {
+        Self { node_idx, skew, expect_accepted: true, _phantom: Default::default() }
+    }
+
+    /// Expect the node to reject the payload attributes
+    pub const fn expect_rejected(mut self) -> Self {
+        self.expect_accepted = false;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for SimulateClockSkew<Engine>
+where
+    Engine: EngineTypes + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+
+            let timestamp = env.latest_header_time + self.skew.as_secs();
+            let payload_attributes = PayloadAttributes {
+                timestamp,
+                prev_randao: B256::random(),
+                suggested_fee_recipient: Address::random(),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: Some(B256::ZERO),
+            };
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(self.node_idx).await?,
+                fork_choice_state,
+                Some(payload_attributes),
+            )
+            .await;
+            let (accepted, outcome) = match result {
+                Ok(response) => match response.payload_id {
+                    Some(payload_id) => (true, format!("accepted with payload ID {payload_id}")),
+                    None => {
+                        return Err(eyre::eyre!(
+                            "Expected a payload ID or invalid payload attributes error ({INVALID_PAYLOAD_ATTRIBUTES_CODE}) for timestamp {timestamp}, got status {:?}",
+                            response.payload_status.status
+                        ))
+                    }
+                },
+                Err(jsonrpsee::core::client::Error::Call(error))
+                    if error.code() == INVALID_PAYLOAD_ATTRIBUTES_CODE =>
+                {
+                    (false, format!("rejected: {}", error.message()))
+                }
+                Err(err) => {
+                    return Err(eyre::eyre!(
+                        "Expected a payload ID or invalid payload attributes error ({INVALID_PAYLOAD_ATTRIBUTES_CODE}) for timestamp {timestamp}, got: {err}"
+                    ))
+                }
+            };
+
+            if accepted != self.expect_accepted {
+                return Err(eyre::eyre!(
+                    "Payload attributes with timestamp {timestamp} ({:?} ahead of parent) were {outcome}",
+                    self.skew
+                ));
+            }
+
+            debug!("Payload attributes {:?} ahead of parent were {outcome}", self.skew);
+            Ok(())
+        })
+    }
+}
+
+/// Action that waits until a node has reorged at least `min_depth` blocks away from a captured
+/// pre-reorg head.
+///
+/// The reorg depth is the distance between the captured head and the common ancestor of its
+/// branch with the node's canonical chain. The node is polled until the depth reaches
+/// `min_depth` or the timeout expires, the last observed depth is stored in
+/// [`observed_depth`](Self::observed_depth).
+///
+/// Blocks of the old branch are resolved by hash, so the action should be started before the
+/// reorg or the node must still serve the old branch.
+#[derive(Debug)]
+pub struct WaitForChainReorgDepth {
+    /// The node index to poll
+    pub node_idx: usize,
+    /// Head of the branch captured before the reorg
+    pub pre_reorg_head: LatestBlockInfo,
+    /// Minimum reorg depth to wait for
+    pub min_depth: u64,
+    /// Maximum time to wait for the reorg
+    pub timeout: Duration,
+    /// Interval between polls
+    pub poll_interval: Duration,
+    /// Reorg depth observed by the last poll
+    pub observed_depth: Option<u64>,
+}
+
+impl WaitForChainReorgDepth {
+    /// Create a new `WaitForChainReorgDepth` action
+    pub const fn new(node_idx: usize, pre_reorg_head: LatestBlockInfo, min_depth: u64) -> Self {
+        Self {
+            node_idx,
+            pre_reorg_head,
+            min_depth,
+            timeout: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(500),
+            observed_depth: None,
+        }
+    }
+
+    /// Set the maximum time to wait for the reorg
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the interval between polls
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl<I> Action<I> for WaitForChainReorgDepth
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+            let head = self.pre_reorg_head.clone();
+
+            // hashes of the old branch, indexed by distance from the captured head
+            let mut old_branch = vec![head.hash];
+            let deadline = Instant::now() + self.timeout;
+
+            loop {
+                let depth = reorg_depth(&node_client.rpc, &head, &mut old_branch).await?;
+                self.observed_depth = Some(depth);
+
+                if depth >= self.min_depth {
+                    debug!(
+                        "Node {} reorged {depth} blocks away from {} (block {})",
+                        self.node_idx, head.hash, head.number
+                    );
+                    return Ok(());
+                }
+                if Instant::now() >= deadline {
+                    return Err(eyre::eyre!(
+                        "Node {} reorged {depth} blocks away from {} within {:?}, expected at least {}",
+                        self.node_idx,
+                        head.hash,
+                        self.timeout,
+                        self.min_depth
+                    ));
+                }
+                sleep(self.poll_interval).await;
+            }
+        })
+    }
+}
+
+/// Returns the distance between `head` and the common ancestor of its branch with the node's
+/// canonical chain, zero if `head` is still canonical.
+///
+/// `old_branch` caches the hashes of the branch of `head`, indexed by distance from `head`. It
+/// must start with the hash of `head` and is extended with parent hashes fetched by hash as
+/// needed.
+async fn reorg_depth(
+    rpc: &HttpClient,
+    head: &LatestBlockInfo,
+    old_branch: &mut Vec<B256>,
+) -> Result<u64> {
+    let mut depth = 0;
+    while depth < head.number {
+        if old_branch.len() <= depth as usize {
+            let child = *old_branch.last().expect("not empty");
+            let parent_hash = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_hash(
+                rpc, child, false,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Block {child} of the pre-reorg branch not found"))?
+            .header
+            .parent_hash;
+            old_branch.push(parent_hash);
+        }
+
+        let canonical = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+            rpc,
+            BlockNumberOrTag::Number(head.number - depth),
+            false,
+        )
+        .await?
+        .map(|block| block.header.hash);
+        if canonical == Some(old_branch[depth as usize]) {
+            break;
+        }
+        depth += 1;
+    }
+    Ok(depth)
+}
+
+/// Engine API error code for an unknown payload id.
+const UNKNOWN_PAYLOAD_CODE: i32 = -38001;
+
+/// Engine API error code for invalid payload attributes.
+const INVALID_PAYLOAD_ATTRIBUTES_CODE: i32 = -38003;
+
+/// Engine API error code for a method called with a payload of a fork it does not support.
+const UNSUPPORTED_FORK_CODE: i32 = -38005;
+
+/// Action that requests a payload with an unknown id via `engine_getPayloadV3` and asserts the
+/// node rejects it with the unknown payload error (code `-38001`).
+#[derive(Debug)]
+pub struct AssertEngineGetPayloadUnknownId<Engine> {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The payload id to request, a random id is used if unset
+    pub payload_id: Option<PayloadId>,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertEngineGetPayloadUnknownId<Engine> {
+    /// Create a new `AssertEngineGetPayloadUnknownId` action requesting a random payload id
+    pub fn new(node_idx: usize) -> Self {
+        Self { node_idx, payload_id: None, _phantom: Default::default() }
+    }
+
+    /// Set the payload id to request, e.g. an expired one
+    pub const fn with_payload_id(mut self, payload_id: PayloadId) -> Self {
+        self.payload_id = Some(payload_id);
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for AssertEngineGetPayloadUnknownId<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.node_idx >= env.node_clients.len() {
+                return Err(eyre::eyre!("Node index out of bounds: {}", self.node_idx));
+            }
+            let payload_id = self.payload_id.unwrap_or_else(|| PayloadId::new(rand::random()));
+
+            let engine_client = env.engine_client(self.node_idx).await?;
+            match EngineApiClient::<Engine>::get_payload_v3(engine_client, payload_id).await {
+                Ok(_) => Err(eyre::eyre!(
+                    "Node {} returned a payload for unknown payload id {payload_id}",
+                    self.node_idx
+                )),
+                Err(jsonrpsee::core::client::Error::Call(error))
+                    if error.code() == UNKNOWN_PAYLOAD_CODE =>
+                {
+                    debug!(
+                        "Node {} rejected unknown payload id {payload_id}: {}",
+                        self.node_idx,
+                        error.message()
+                    );
+                    Ok(())
+                }
+                Err(err) => Err(eyre::eyre!(
+                    "Expected unknown payload error ({UNKNOWN_PAYLOAD_CODE}) for payload id {payload_id}, got: {err}"
+                )),
+            }
+        })
+    }
+}
+
+/// Action that submits a transaction to a node and measures how long it takes to reach the pool
+/// of every other node.
+///
+/// The pools are polled until the transaction appears in all of them, the maximum latency must
+/// stay within `budget`. The per-node latencies are stored in
+/// [`Environment::propagation_latencies`].
+#[derive(Debug)]
+pub struct AssertTransactionPropagationLatency {
+    /// The node index to submit the transaction to
+    pub node_idx: usize,
+    /// The raw transaction to submit
+    pub raw_tx: Bytes,
+    /// Maximum time the transaction may take to reach every node
+    pub budget: Duration,
+    /// Interval between pool polls
+    pub poll_interval: Duration,
+}
+
+impl AssertTransactionPropagationLatency {
+    /// Create a new `AssertTransactionPropagationLatency` action submitting to node 0
+    pub const fn new(raw_tx: Bytes, budget: Duration) -> Self {
+        Self { node_idx: 0, raw_tx, budget, poll_interval: Duration::from_millis(50) }
+    }
+
+    /// Set the node index to submit the transaction to
+    pub const fn with_node(mut self, node_idx: usize) -> Self {
+        self.node_idx = node_idx;
+        self
+    }
+
+    /// Set the interval between pool polls
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertTransactionPropagationLatency
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let start = Instant::now();
+            let tx_hash =
+                EthApiClient::<Transaction, Block, Receipt, Header>::send_raw_transaction(
+                    &node_client.rpc,
+                    self.raw_tx.clone(),
+                )
+                .await?;
+            debug!("Submitted transaction {tx_hash} to node {}", self.node_idx);
+
+            let mut pending =
+                (0..env.node_clients.len()).filter(|idx| *idx != self.node_idx).collect::<Vec<_>>();
+            let mut latencies = HashMap::new();
+
+            while !pending.is_empty() {
+                let mut still_pending = Vec::new();
+                for idx in pending {
+                    let content =
+                        TxPoolApiClient::<Transaction>::txpool_content(&env.node_clients[idx].rpc)
+                            .await?;
+                    let found = content
+                        .pending
+                        .values()
+                        .chain(content.queued.values())
+                        .flat_map(|txs| txs.values())
+                        .any(|tx| *tx.inner.tx_hash() == tx_hash);
+                    if found {
+                        latencies.insert(idx, start.elapsed());
+                    } else {
+                        still_pending.push(idx);
+                    }
+                }
+                pending = still_pending;
+
+                if !pending.is_empty() {
+                    if start.elapsed() > self.budget {
+                        env.propagation_latencies = latencies;
+                        return Err(eyre::eyre!(
+                            "Transaction {tx_hash} did not reach nodes {pending:?} within {:?}",
+                            self.budget
+                        ));
+                    }
+                    sleep(self.poll_interval).await;
+                }
+            }
+
+            let slowest = latencies.iter().max_by_key(|(_, latency)| **latency);
+            if let Some((idx, latency)) = slowest {
+                if *latency > self.budget {
+                    let (idx, latency) = (*idx, *latency);
+                    env.propagation_latencies = latencies;
+                    return Err(eyre::eyre!(
+                        "Transaction {tx_hash} reached node {idx} after {latency:?}, exceeding the budget of {:?}",
+                        self.budget
+                    ));
+                }
+                debug!("Transaction {tx_hash} propagated to all nodes, slowest was node {idx} after {latency:?}");
+            }
+
+            env.propagation_latencies = latencies;
+            Ok(())
+        })
+    }
+}
+
+/// Prometheus metric reporting the size of each database table, in bytes.
+const DB_TABLE_SIZE_METRIC: &str = "reth_db_table_size";
+
+/// Action that measures how much a node's database grew while running an inner action.
+///
+/// The size of the database tables is scraped from the node's metrics endpoint before and after
+/// the inner action, and divided by the number of blocks the node's chain advanced. The
+/// measurement is stored in [`Environment::state_growth`] under `label`.
+#[expect(missing_debug_implementations)]
+pub struct MeasureStateGrowth<I> {
+    /// The node index to measure
+    pub node_idx: usize,
+    /// Label under which the measurement is stored
+    pub label: String,
+    /// Action producing the blocks to measure
+    pub inner: Box<dyn Action<I>>,
+}
+
+impl<I> MeasureStateGrowth<I> {
+    /// Create a new `MeasureStateGrowth` action
+    pub fn new<A: Action<I>>(node_idx: usize, label: impl Into<String>, inner: A) -> Self {
+        Self { node_idx, label: label.into(), inner: Box::new(inner) }
+    }
+}
+
+impl<I> Action<I> for MeasureStateGrowth<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let metrics = [DB_TABLE_SIZE_METRIC.to_string()];
+            let node_idx = self.node_idx;
+            let node_rpc = move |env: &Environment<I>| {
+                env.node_clients
+                    .get(node_idx)
+                    .map(|client| client.rpc.clone())
+                    .ok_or_else(|| eyre::eyre!("Node index out of bounds: {node_idx}"))
+            };
+
+            let rpc = node_rpc(env)?;
+            let block_before =
+                EthApiClient::<Transaction, Block, Receipt, Header>::block_number(&rpc).await?;
+            let size_before = db_size(&scrape_metrics(env, self.node_idx, &metrics).await?)?;
+
+            self.inner.execute(env).await?;
+
+            let rpc = node_rpc(env)?;
+            let block_after =
+                EthApiClient::<Transaction, Block, Receipt, Header>::block_number(&rpc).await?;
+            let size_after = db_size(&scrape_metrics(env, self.node_idx, &metrics).await?)?;
+
+            let growth = StateGrowth {
+                size_before,
+                size_after,
+                blocks: block_after.saturating_sub(block_before).to(),
+            };
+            match growth.bytes_per_block() {
+                Some(bytes_per_block) => debug!(
+                    "{}: database grew by {} bytes over {} blocks, {bytes_per_block:.0} bytes per block",
+                    self.label,
+                    growth.delta(),
+                    growth.blocks
+                ),
+                None => debug!(
+                    "{}: database grew by {} bytes, no blocks were added",
+                    self.label,
+                    growth.delta()
+                ),
+            }
+
+            env.state_growth.insert(self.label.clone(), growth);
+            Ok(())
+        })
+    }
+}
+
+/// Returns the total size of the database tables in the snapshot.
+fn db_size(snapshot: &MetricsSnapshot) -> Result<u64> {
+    if snapshot.is_empty() {
+        return Err(eyre::eyre!("No {DB_TABLE_SIZE_METRIC} samples reported by the node"));
+    }
+    Ok(snapshot.values().sum::<f64>() as u64)
+}
+
+/// Action that recovers the sender of a mined transaction and asserts it matches the expected
+/// address.
+///
+/// The signed transaction returned by the node is wrapped in an [`ExtendedTxEnvelope`] and its
+/// signer recovered locally. Both the recovered signer and the `from` field reported by the node
+/// must equal `expected`.
+#[derive(Debug)]
+pub struct AssertSenderRecovery {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the transaction
+    pub tx_hash: B256,
+    /// Expected sender of the transaction
+    pub expected: Address,
+}
+
+impl AssertSenderRecovery {
+    /// Create a new `AssertSenderRecovery` action
+    pub const fn new(node_idx: usize, tx_hash: B256, expected: Address) -> Self {
+        Self { node_idx, tx_hash, expected }
+    }
+}
+
+impl<I> Action<I> for AssertSenderRecovery
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let tx = EthApiClient::<Transaction, Block, Receipt, Header>::transaction_by_hash(
+                &node_client.rpc,
+                self.tx_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Transaction {} not found", self.tx_hash))?;
+            if tx.block_hash.is_none() {
+                return Err(eyre::eyre!("Transaction {} is not mined", self.tx_hash));
+            }
+
+            let reported = tx.inner.signer();
+            let envelope =
+                ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::BuiltIn(tx.inner.into_inner());
+            let recovered = envelope.recover_signer().map_err(|err| {
+                eyre::eyre!("Failed to recover signer of transaction {}: {err}", self.tx_hash)
+            })?;
+
+            if recovered != self.expected {
+                return Err(eyre::eyre!(
+                    "Recovered signer {recovered} of transaction {} does not match expected {}",
+                    self.tx_hash,
+                    self.expected
+                ));
+            }
+            if reported != self.expected {
+                return Err(eyre::eyre!(
+                    "Node reported sender {reported} for transaction {}, expected {}",
+                    self.tx_hash,
+                    self.expected
+                ));
+            }
+
+            debug!("Recovered sender {recovered} of transaction {}", self.tx_hash);
+            Ok(())
+        })
+    }
+}
+
+/// Action that gracefully shuts down every node and asserts they exited cleanly.
+///
+/// A node exited cleanly if its tasks finished within `shutdown_timeout` and none of its critical
+/// tasks panicked while it was running. All nodes are shut down before the failures, listing the
+/// nodes that had to be forcefully stopped or panicked, are reported. Nodes that were not launched
+/// by the setup or are already shut down are skipped.
+#[derive(Debug)]
+pub struct GracefulShutdown {
+    /// Maximum time to wait for each node to shut down
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for GracefulShutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GracefulShutdown {
+    /// Create a new `GracefulShutdown` action
+    pub const fn new() -> Self {
+        Self { shutdown_timeout: Duration::from_secs(10) }
+    }
+
+    /// Set the maximum time to wait for each node to shut down
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+}
+
+impl<I> Action<I> for GracefulShutdown
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut forced = Vec::new();
+            let mut panicked = Vec::new();
+
+            for (idx, node_client) in env.node_clients.iter_mut().enumerate() {
+                let Some(lifecycle) = node_client.lifecycle.as_mut() else {
+                    debug!("Node {idx} was not launched by the setup, skipping");
+                    continue
+                };
+                if !lifecycle.is_running() {
+                    debug!("Node {idx} is already shut down, skipping");
+                    continue
+                }
+
+                if !lifecycle.shutdown(self.shutdown_timeout).await? {
+                    forced.push(idx);
+                }
+                let panics = lifecycle.panicked_tasks();
+                if !panics.is_empty() {
+                    panicked.push((idx, panics));
+                }
+            }
+
+            if !forced.is_empty() || !panicked.is_empty() {
+                return Err(eyre::eyre!(
+                    "Nodes did not shut down cleanly, forcefully stopped after {:?}: {forced:?}, panicked: {panicked:?}",
+                    self.shutdown_timeout
+                ));
+            }
+
+            debug!("All nodes shut down cleanly");
+            Ok(())
+        })
+    }
+}
+
+/// Action that records the transactions of the latest blocks of a node, runs an inner action
+/// reorging those blocks away, and asserts their transactions were not silently dropped.
+///
+/// Every recorded transaction must either be included in the new canonical chain or be back in
+/// the node's pool. Transactions whose nonce was consumed on the new chain by another transaction
+/// are no longer valid and are skipped.
+#[expect(missing_debug_implementations)]
+pub struct AssertReorgPreservesTransactions<I> {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Number of blocks from the head whose transactions are recorded
+    pub depth: u64,
+    /// Action triggering the reorg
+    pub inner: Box<dyn Action<I>>,
+}
+
+impl<I> AssertReorgPreservesTransactions<I> {
+    /// Create a new `AssertReorgPreservesTransactions` action
+    pub fn new<A: Action<I>>(node_idx: usize, depth: u64, inner: A) -> Self {
+        Self { node_idx, depth, inner: Box::new(inner) }
+    }
+}
+
+impl<I> Action<I> for AssertReorgPreservesTransactions<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.depth == 0 {
+                return Err(eyre::eyre!("Reorg depth must be greater than zero"));
+            }
+            let node_idx = self.node_idx;
+            let node_rpc = move |env: &Environment<I>| {
+                env.node_clients
+                    .get(node_idx)
+                    .map(|client| client.rpc.clone())
+                    .ok_or_else(|| eyre::eyre!("Node index out of bounds: {node_idx}"))
+            };
+
+            // record the branch: the hash of its first block and its transactions
+            let rpc = node_rpc(env)?;
+            let head: u64 =
+                EthApiClient::<Transaction, Block, Receipt, Header>::block_number(&rpc).await?.to();
+            let first = head.saturating_sub(self.depth - 1).max(1);
+            let mut branch_root = None;
+            let mut recorded = Vec::new();
+            for number in first..=head {
+                let block = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                    &rpc,
+                    BlockNumberOrTag::Number(number),
+                    true,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+                branch_root.get_or_insert(block.header.hash);
+                recorded.extend(
+                    block
+                        .transactions
+                        .into_transactions()
+                        .map(|tx| (*tx.inner.tx_hash(), tx.inner.signer(), tx.inner.nonce())),
+                );
+            }
+            let branch_root = branch_root.expect("branch is not empty");
+            debug!("Recorded {} transactions in blocks {first}..={head}", recorded.len());
+
+            self.inner.execute(env).await?;
+
+            let rpc = node_rpc(env)?;
+            let canonical = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &rpc,
+                BlockNumberOrTag::Number(first),
+                false,
+            )
+            .await?
+            .map(|block| block.header.hash);
+            if canonical == Some(branch_root) {
+                return Err(eyre::eyre!(
+                    "Block {first} of the recorded branch is still canonical, no reorg happened"
+                ));
+            }
+
+            let content = TxPoolApiClient::<Transaction>::txpool_content(&rpc).await?;
+            let pooled = content
+                .pending
+                .values()
+                .chain(content.queued.values())
+                .flat_map(|txs| txs.values())
+                .map(|tx| *tx.inner.tx_hash())
+                .collect::<HashSet<_>>();
+
+            let mut dropped = Vec::new();
+            for (hash, sender, nonce) in recorded {
+                if pooled.contains(&hash) {
+                    debug!("Transaction {hash} returned to the pool");
+                    continue
+                }
+                let included =
+                    EthApiClient::<Transaction, Block, Receipt, Header>::transaction_by_hash(
+                        &rpc, hash,
+                    )
+                    .await?
+                    .and_then(|tx| tx.block_number);
+                if let Some(block_number) = included {
+                    debug!("Transaction {hash} re-included in block {block_number}");
+                    continue
+                }
+                let account_nonce =
+                    EthApiClient::<Transaction, Block, Receipt, Header>::transaction_count(
+                        &rpc,
+                        sender,
+                        Some(BlockId::Number(BlockNumberOrTag::Latest)),
+                    )
+                    .await?;
+                if account_nonce > U256::from(nonce) {
+                    debug!(
+                        "Transaction {hash} is no longer valid, nonce {nonce} of {sender} was used"
+                    );
+                    continue
+                }
+                dropped.push(hash);
+            }
+
+            if !dropped.is_empty() {
+                return Err(eyre::eyre!(
+                    "Transactions dropped by the reorg on node {}: {dropped:?}",
+                    self.node_idx
+                ));
+            }
+
+            debug!("No transaction was dropped by the reorg on node {}", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that fires many concurrent engine API requests at a single node and asserts every
+/// request completes with a coherent response.
+///
+/// The requests cycle through:
+///  - forkchoice updates to the latest block, which must be `VALID`
+///  - forkchoice updates with payload attributes, which must return the same payload id
+///  - `engine_getPayloadV3` for that payload id, one of which must resolve the payload while the
+///    others may fail with code `-38001` once it was resolved
+///  - `engine_getPayloadV3` for an unknown payload id, which must fail with code `-38001`
+///
+/// Each request must complete within `request_timeout`, and the node must still answer a
+/// forkchoice update once all requests completed. Payload attributes for the next block must
+/// have been generated.
+#[derive(Debug)]
+pub struct ConcurrentEngineStressTest<Engine> {
+    /// The node index to send the requests to
+    pub node_idx: usize,
+    /// Number of concurrent requests
+    pub num_requests: usize,
+    /// Maximum time a single request may take
+    pub request_timeout: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> ConcurrentEngineStressTest<Engine> {
+    /// Create a new `ConcurrentEngineStressTest` action
+    pub fn new(node_idx: usize, num_requests: usize) -> Self {
+        Self {
+            node_idx,
+            num_requests,
+            request_timeout: Duration::from_secs(10),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the maximum time a single request may take
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+}
+
+/// Kind of request sent by [`ConcurrentEngineStressTest`].
+#[derive(Debug, Clone, Copy)]
+enum EngineStressRequest {
+    ForkchoiceUpdated,
+    ForkchoiceUpdatedWithAttributes,
+    GetPayload,
+    GetUnknownPayload,
+}
+
+impl<Engine> Action<Engine> for ConcurrentEngineStressTest<Engine>
+where
+    Engine: EngineTypes + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let engine = env.engine_client(self.node_idx).await?;
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let payload_id = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                engine,
+                fork_choice_state,
+                Some(payload_attributes.clone()),
+            )
+            .await?
+            .payload_id
+            .ok_or_else(|| eyre::eyre!("No payload ID returned from forkChoiceUpdated"))?;
+            let unknown_payload_id = loop {
+                let id = PayloadId::new(rand::random());
+                if id != payload_id {
+                    break id
+                }
+            };
+
+            let kinds = [
+                EngineStressRequest::ForkchoiceUpdated,
+                EngineStressRequest::ForkchoiceUpdatedWithAttributes,
+                EngineStressRequest::GetPayload,
+                EngineStressRequest::GetUnknownPayload,
+            ];
+            let request_timeout = self.request_timeout;
+            let resolved = AtomicUsize::new(0);
+            let resolved = &resolved;
+            let requests = (0..self.num_requests).map(|idx| {
+                let kind = kinds[idx % kinds.len()];
+                let payload_attributes = payload_attributes.clone();
+                async move {
+                    let request = async {
+                        match kind {
+                            EngineStressRequest::ForkchoiceUpdated => {
+                                let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                                    engine,
+                                    fork_choice_state,
+                                    None,
+                                )
+                                .await?
+                                .payload_status
+                                .status;
+                                if !status.is_valid() {
+                                    return Err(eyre::eyre!("expected VALID, got {status:?}"))
+                                }
+                            }
+                            EngineStressRequest::ForkchoiceUpdatedWithAttributes => {
+                                let id = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                                    engine,
+                                    fork_choice_state,
+                                    Some(payload_attributes),
+                                )
+                                .await?
+                                .payload_id;
+                                if id != Some(payload_id) {
+                                    return Err(eyre::eyre!(
+                                        "expected payload ID {payload_id}, got {id:?}"
+                                    ))
+                                }
+                            }
+                            EngineStressRequest::GetPayload => {
+                                // resolving a payload removes its job, so only one of the
+                                // concurrent requests for the same id gets the payload
+                                match EngineApiClient::<Engine>::get_payload_v3(engine, payload_id)
+                                    .await
+                                {
+                                    Ok(_) => {
+                                        resolved.fetch_add(1, Ordering::Relaxed);
+                                    }
+                                    Err(jsonrpsee::core::client::Error::Call(error))
+                                        if error.code() == UNKNOWN_PAYLOAD_CODE => {}
+                                    Err(err) => return Err(err.into()),
+                                }
+                            }
+                            EngineStressRequest::GetUnknownPayload => {
+                                match EngineApiClient::<Engine>::get_payload_v3(
+                                    engine,
+                                    unknown_payload_id,
+                                )
+                                .await
+                                {
+                                    Err(jsonrpsee::core::client::Error::Call(error))
+                                        if error.code() == UNKNOWN_PAYLOAD_CODE => {}
+                                    Ok(_) => {
+                                        return Err(eyre::eyre!(
+                                            "returned a payload for unknown payload ID {unknown_payload_id}"
+                                        ))
+                                    }
+                                    Err(err) => {
+                                        return Err(eyre::eyre!(
+                                            "expected unknown payload error, got: {err}"
+                                        ))
+                                    }
+                                }
+                            }
+                        }
+                        Ok::<_, eyre::Report>(())
+                    };
+                    let outcome = match tokio::time::timeout(request_timeout, request).await {
+                        Ok(result) => result,
+                        Err(_) => Err(eyre::eyre!("hung for more than {request_timeout:?}")),
+                    };
+                    (idx, kind, outcome)
+                }
+            });
+
+            let failures = join_all(requests)
+                .await
+                .into_iter()
+                .filter_map(|(idx, kind, outcome)| {
+                    outcome.err().map(|err| format!("request {idx} ({kind:?}): {err}"))
+                })
+                .collect::<Vec<_>>();
+            if !failures.is_empty() {
+                return Err(eyre::eyre!(
+                    "{} of {} concurrent engine requests to node {} failed:\n{}",
+                    failures.len(),
+                    self.num_requests,
+                    self.node_idx,
+                    failures.join("\n")
+                ));
+            }
+            if self.num_requests > 2 && resolved.load(Ordering::Relaxed) == 0 {
+                return Err(eyre::eyre!(
+                    "No engine_getPayloadV3 request resolved payload {payload_id} on node {}",
+                    self.node_idx
+                ));
+            }
+
+            let status = tokio::time::timeout(
+                self.request_timeout,
+                EngineApiClient::<Engine>::fork_choice_updated_v3(engine, fork_choice_state, None),
+            )
+            .await
+            .map_err(|_| {
+                eyre::eyre!("Node {} is unresponsive after the stress test", self.node_idx)
+            })??
+            .payload_status
+            .status;
+            if !status.is_valid() {
+                return Err(eyre::eyre!(
+                    "Node {} reported {status:?} for its head after the stress test",
+                    self.node_idx
+                ));
+            }
+
+            debug!(
+                "Node {} handled {} concurrent engine requests",
+                self.node_idx, self.num_requests
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that waits until a node's canonical chain reaches a target block number.
+#[derive(Debug)]
+pub struct WaitForBlock {
+    /// The node index to poll
+    pub node_idx: usize,
+    /// Block number to wait for
+    pub target_number: u64,
+    /// Maximum time to wait for the block
+    pub timeout: Duration,
+    /// Interval between polls
+    pub poll_interval: Duration,
+}
+
+impl WaitForBlock {
+    /// Create a new `WaitForBlock` action
+    pub const fn new(node_idx: usize, target_number: u64, timeout: Duration) -> Self {
+        Self { node_idx, target_number, timeout, poll_interval: Duration::from_millis(250) }
+    }
+
+    /// Set the interval between polls
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl<I> Action<I> for WaitForBlock
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let wait = async {
+                loop {
+                    let latest =
+                        EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                            &node_client.rpc,
+                            BlockNumberOrTag::Latest,
+                            false,
+                        )
+                        .await?
+                        .ok_or_else(|| eyre::eyre!("No latest block found"))?
+                        .header
+                        .number;
+
+                    if latest >= self.target_number {
+                        return Ok::<_, eyre::Report>(latest)
+                    }
+                    debug!(
+                        "Node {} at block {latest}, waiting for block {}",
+                        self.node_idx, self.target_number
+                    );
+                    sleep(self.poll_interval).await;
+                }
+            };
+
+            let latest = tokio::time::timeout(self.timeout, wait).await.map_err(|_| {
+                eyre::eyre!(
+                    "Node {} did not reach block {} within {:?}",
+                    self.node_idx,
+                    self.target_number,
+                    self.timeout
+                )
+            })??;
+
+            debug!("Node {} reached block {latest}", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that reorgs the chain back to an ancestor of the current head and builds a divergent
+/// branch on top of it.
+///
+/// A forkchoice update to a canonical ancestor does not move the head, so the competing branch is
+/// built explicitly: `new_blocks` empty blocks are chained on top of `target_hash` and submitted
+/// to every node with `engine_newPayloadV3`, then the forkchoice of every node is pointed at the
+/// tip of the branch. The action fails if `target_hash` is not a canonical ancestor of the
+/// current head, or if the head did not change.
+///
+/// The head before the reorg is recorded in `env.pre_reorg_head` for [`AssertReorgDepth`].
+#[derive(Debug)]
+pub struct Reorg<Engine> {
+    /// Hash of the ancestor to reorg to
+    pub target_hash: B256,
+    /// Number of blocks to build on top of the ancestor
+    pub new_blocks: u64,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> Reorg<Engine> {
+    /// Create a new `Reorg` action
+    pub fn new(target_hash: B256, new_blocks: u64) -> Self {
+        Self { target_hash, new_blocks, _phantom: Default::default() }
+    }
+}
+
+/// Builds an empty block on top of `parent`.
+///
+/// The block has no transactions and no withdrawals, so executing it leaves the state of
+/// `parent` unchanged. `extra_data` tells the block apart from a sibling with the same timestamp.
+fn empty_block_on(
+    parent: &alloy_consensus::Header,
+    parent_hash: B256,
+    timestamp: u64,
+    extra_data: Bytes,
+) -> Result<reth_ethereum_primitives::Block> {
+    let base_fee_per_gas = parent
+        .next_block_base_fee(BaseFeeParams::ethereum())
+        .ok_or_else(|| eyre::eyre!("Block {} has no base fee", parent.number))?;
+    let excess_blob_gas = BlobParams::cancun().next_block_excess_blob_gas(
+        parent.excess_blob_gas.unwrap_or_default(),
+        parent.blob_gas_used.unwrap_or_default(),
+    );
+    let header = alloy_consensus::Header {
+        parent_hash,
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        state_root: parent.state_root,
+        transactions_root: EMPTY_ROOT_HASH,
+        receipts_root: EMPTY_ROOT_HASH,
+        withdrawals_root: Some(EMPTY_ROOT_HASH),
+        number: parent.number + 1,
+        gas_limit: parent.gas_limit,
+        timestamp,
+        extra_data,
+        mix_hash: B256::random(),
+        base_fee_per_gas: Some(base_fee_per_gas),
+        blob_gas_used: Some(0),
+        excess_blob_gas: Some(excess_blob_gas),
+        parent_beacon_block_root: Some(B256::ZERO),
+        ..Default::default()
+    };
+
+    Ok(reth_ethereum_primitives::Block {
+        header,
+        body: reth_ethereum_primitives::BlockBody {
+            transactions: vec![],
+            ommers: vec![],
+            withdrawals: Some(Default::default()),
+        },
+    })
+}
+
+impl<Engine> Action<Engine> for Reorg<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.new_blocks == 0 {
+                return Err(eyre::eyre!("Reorg must build at least one block"));
+            }
+            let rpc = &env
+                .node_clients
+                .first()
+                .ok_or_else(|| eyre::eyre!("No node clients available"))?
+                .rpc;
+
+            let tip = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found"))?;
+            let target = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(
+                rpc,
+                self.target_hash,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Reorg target {} not found", self.target_hash))?;
+            let canonical = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                rpc,
+                BlockNumberOrTag::Number(target.number),
+            )
+            .await?
+            .map(|header| header.hash);
+            if target.number >= tip.number || canonical != Some(target.hash) {
+                return Err(eyre::eyre!(
+                    "Reorg target {} ({}) is not an ancestor of the current head {} ({})",
+                    target.number,
+                    target.hash,
+                    tip.number,
+                    tip.hash
+                ));
+            }
+
+            let mut parent = target.inner.clone();
+            let mut parent_hash = target.hash;
+            for _ in 0..self.new_blocks {
+                let block = empty_block_on(
+                    &parent,
+                    parent_hash,
+                    parent.timestamp + env.block_timestamp_increment,
+                    Bytes::from_static(b"reorg"),
+                )?;
+                let payload = ExecutionPayloadV3::from_block_slow(&block);
+                let block_hash = payload.payload_inner.payload_inner.block_hash;
+                for idx in 0..env.node_clients.len() {
+                    let status = EngineApiClient::<Engine>::new_payload_v3(
+                        env.engine_client(idx).await?,
+                        payload.clone(),
+                        vec![],
+                        B256::ZERO,
+                    )
+                    .await?
+                    .status;
+                    if !matches!(status, PayloadStatusEnum::Valid | PayloadStatusEnum::Accepted) {
+                        return Err(eyre::eyre!(
+                            "Node {idx}: Competing block {} ({block_hash}) not accepted: {status:?}",
+                            block.header.number
+                        ));
+                    }
+                }
+                parent = block.header;
+                parent_hash = block_hash;
+            }
+
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: parent_hash,
+                safe_block_hash: B256::ZERO,
+                finalized_block_hash: B256::ZERO,
+            };
+            for idx in 0..env.node_clients.len() {
+                let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                    env.engine_client(idx).await?,
+                    fork_choice_state,
+                    None,
+                )
+                .await?
+                .payload_status
+                .status;
+                if !status.is_valid() {
+                    return Err(eyre::eyre!(
+                        "Node {idx}: Forkchoice update to competing head {parent_hash} returned \
+                         {status:?}"
+                    ));
+                }
+            }
+
+            let rpc = &env.node_clients[0].rpc;
+            let new_tip = EthApiClient::<Transaction, Block, Receipt, Header>::header_by_number(
+                rpc,
+                BlockNumberOrTag::Latest,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest header found"))?;
+            if new_tip.hash != parent_hash {
+                return Err(eyre::eyre!(
+                    "Head {} ({}) is not the competing head {} ({parent_hash}) after the reorg",
+                    new_tip.number,
+                    new_tip.hash,
+                    parent.number
+                ));
+            }
+
+            debug!(
+                "Reorged from {} ({}) onto {} ({}) forked at {} ({})",
+                tip.number, tip.hash, new_tip.number, new_tip.hash, target.number, target.hash
+            );
+
+            env.pre_reorg_head = Some(LatestBlockInfo { hash: tip.hash, number: tip.number });
+            env.latest_fork_choice_state = fork_choice_state;
+            env.latest_block_info =
+                Some(LatestBlockInfo { hash: new_tip.hash, number: new_tip.number });
+            env.latest_header_time = new_tip.timestamp;
+            Ok(())
+        })
+    }
+}
+
+/// Action that submits a raw transaction to a node's pool and records its hash in
+/// [`Environment::last_tx_hashes`].
+///
+/// If the node rejects the transaction, the error returned by the node is reported as is.
+#[derive(Debug)]
+pub struct SendRawTransaction {
+    /// The node index to submit the transaction to
+    pub node_idx: usize,
+    /// The EIP-2718 encoded transaction
+    pub raw: Bytes,
+}
+
+impl SendRawTransaction {
+    /// Create a new `SendRawTransaction` action
+    pub const fn new(node_idx: usize, raw: Bytes) -> Self {
+        Self { node_idx, raw }
+    }
+}
+
+impl<I> Action<I> for SendRawTransaction
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let tx_hash =
+                EthApiClient::<Transaction, Block, Receipt, Header>::send_raw_transaction(
+                    &node_client.rpc,
+                    self.raw.clone(),
+                )
+                .await
+                .map_err(|err| match err {
+                    jsonrpsee::core::client::Error::Call(error) => {
+                        eyre::eyre!("Node {} rejected the transaction: {error}", self.node_idx)
+                    }
+                    err => eyre::eyre!(
+                        "Failed to send the transaction to node {}: {err}",
+                        self.node_idx
+                    ),
+                })?;
+
+            debug!("Sent transaction {tx_hash} to node {}", self.node_idx);
+            env.last_tx_hashes.push(tx_hash);
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts a transaction was included in a block.
+///
+/// The transaction must have a successful receipt, optionally in the expected block. Reverted
+/// transactions are accepted if [`allow_reverted`](Self::allow_reverted) is set. The block
+/// number is recorded in [`Environment::included_transactions`].
+#[derive(Debug)]
+pub struct AssertTransactionIncluded {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the transaction
+    pub tx_hash: B256,
+    /// Block number the transaction is expected in, any block if unset
+    pub expected_block: Option<u64>,
+    /// Whether a reverted transaction counts as included
+    pub allow_reverted: bool,
+}
+
+impl AssertTransactionIncluded {
+    /// Create a new `AssertTransactionIncluded` action
+    pub const fn new(node_idx: usize, tx_hash: B256) -> Self {
+        Self { node_idx, tx_hash, expected_block: None, allow_reverted: false }
+    }
+
+    /// Set the block number the transaction is expected in
+    pub const fn with_expected_block(mut self, expected_block: u64) -> Self {
+        self.expected_block = Some(expected_block);
+        self
+    }
+
+    /// Accept reverted transactions as included
+    pub const fn allow_reverted(mut self) -> Self {
+        self.allow_reverted = true;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertTransactionIncluded
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let receipt =
+                EthApiClient::<Transaction, Block, TransactionReceipt, Header>::transaction_receipt(
+                    &node_client.rpc,
+                    self.tx_hash,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("No receipt found for transaction {}", self.tx_hash))?;
+
+            let block_number = receipt
+                .block_number
+                .ok_or_else(|| eyre::eyre!("Transaction {} is not included", self.tx_hash))?;
+            if let Some(expected) = self.expected_block {
+                if block_number != expected {
+                    return Err(eyre::eyre!(
+                        "Transaction {} is included in block {block_number}, expected {expected}",
+                        self.tx_hash
+                    ));
+                }
+            }
+            if !receipt.status() && !self.allow_reverted {
+                return Err(eyre::eyre!(
+                    "Transaction {} reverted in block {block_number}",
+                    self.tx_hash
+                ));
+            }
+
+            debug!("Transaction {} is included in block {block_number}", self.tx_hash);
+            env.included_transactions.insert(self.tx_hash, block_number);
+            Ok(())
+        })
+    }
+}
+
+/// Action that runs an inner action, re-running it on failure up to `max_attempts` times.
+///
+/// The delay between attempts starts at `backoff` and doubles after every failed attempt. Only
+/// the error of the last attempt is returned.
+///
+/// The inner action is re-executed on the same instance and [`Environment`], so it must be safe
+/// to re-run after a partial execution: it must not rely on state it consumed or mutated in a
+/// previous attempt.
+#[expect(missing_debug_implementations)]
+pub struct RetryAction<I> {
+    /// Action to run
+    pub inner: Box<dyn Action<I>>,
+    /// Maximum number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub backoff: Duration,
+}
+
+impl<I> RetryAction<I> {
+    /// Create a new `RetryAction` action with an initial backoff of 100ms
+    pub fn new<A: Action<I>>(inner: A, max_attempts: u32) -> Self {
+        Self { inner: Box::new(inner), max_attempts, backoff: Duration::from_millis(100) }
+    }
+
+    /// Set the delay before the first retry
+    pub const fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+}
+
+impl<I> Action<I> for RetryAction<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if self.max_attempts == 0 {
+                return Err(eyre::eyre!("Retry action requires at least one attempt"));
+            }
+
+            let mut backoff = self.backoff;
+            let mut attempt = 1;
+            loop {
+                match self.inner.execute(env).await {
+                    Ok(()) => return Ok(()),
+                    Err(err) if attempt >= self.max_attempts => {
+                        return Err(err.wrap_err(format!("Failed after {attempt} attempts")))
+                    }
+                    Err(err) => {
+                        debug!(
+                            "Attempt {attempt} of {} failed, retrying in {backoff:?}: {err}",
+                            self.max_attempts
+                        );
+                    }
+                }
+                sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+                attempt += 1;
+            }
+        })
+    }
+}
+
+/// Action that runs an inner action and fails if it does not complete within `duration`.
+///
+/// On expiry the inner action is dropped mid-execution, any changes it already made to the
+/// [`Environment`] or the nodes are kept.
+#[expect(missing_debug_implementations)]
+pub struct TimeoutAction<I> {
+    /// Action to run
+    pub inner: Box<dyn Action<I>>,
+    /// Maximum time the inner action may take
+    pub duration: Duration,
+}
+
+impl<I> TimeoutAction<I> {
+    /// Create a new `TimeoutAction` action
+    pub fn new<A: Action<I>>(inner: A, duration: Duration) -> Self {
+        Self { inner: Box::new(inner), duration }
+    }
+}
+
+impl<I> Action<I> for TimeoutAction<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            tokio::time::timeout(self.duration, self.inner.execute(env))
+                .await
+                .map_err(|_| eyre::eyre!("action timed out after {:?}", self.duration))?
+        })
+    }
+}
+
+/// Run a set of actions concurrently.
+///
+/// Every action runs against its own copy of the [`Environment`], which shares the RPC and engine
+/// clients of the nodes but has no websocket clients, lifecycle handles or node launcher. Actions
+/// that subscribe to a node or start and stop nodes therefore cannot run in parallel.
+///
+/// Once all actions completed, only the following fields are merged back into the environment,
+/// in the order of the actions, so a later action wins if two actions write the same key:
+///  - [`Environment::metrics_snapshots`]
+///  - [`Environment::block_ranges`]
+///  - [`Environment::propagation_latencies`]
+///  - [`Environment::state_growth`]
+///  - [`Environment::last_tx_hashes`], appended
+///  - [`Environment::included_transactions`]
+///  - [`Environment::block_metrics`], appended
+///
+/// Changes to any other field, including the chain tracking state, are discarded. If an action
+/// fails, the remaining ones are cancelled and the error is returned.
+#[expect(missing_debug_implementations)]
+pub struct ParallelActions<I> {
+    /// Actions to execute concurrently
+    pub actions: Vec<Box<dyn Action<I>>>,
+}
+
+impl<I> ParallelActions<I> {
+    /// Create a new set of parallel actions
+    pub fn new(actions: Vec<Box<dyn Action<I>>>) -> Self {
+        Self { actions }
+    }
+}
+
+impl<I> Action<I> for ParallelActions<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let branches = self.actions.iter_mut().map(|action| {
+                let mut branch = env.fork_branch();
+                async move {
+                    action.execute(&mut branch).await?;
+                    Ok::<_, eyre::Report>(branch)
+                }
+            });
+            let branches = try_join_all(branches).await?;
+
+            debug!("Completed {} parallel actions", branches.len());
+            for branch in branches {
+                env.merge_branch(branch);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Hook invoked by [`RepeatAction`] with the iteration number before each run.
+pub type RepeatHook<I> = Box<dyn FnMut(u64, &mut Environment<I>) + Send>;
+
+/// Action that runs an inner action a fixed number of times, stopping at the first error.
+///
+/// An optional hook is invoked with the iteration number, starting at `0`, before each run, e.g.
+/// to adjust the payload attributes of the next block.
+#[expect(missing_debug_implementations)]
+pub struct RepeatAction<I> {
+    /// Action to run
+    pub inner: Box<dyn Action<I>>,
+    /// Number of times to run the inner action
+    pub times: u64,
+    /// Hook invoked before each run
+    pub before_each: Option<RepeatHook<I>>,
+}
+
+impl<I> RepeatAction<I> {
+    /// Create a new `RepeatAction` action
+    pub fn new<A: Action<I>>(inner: A, times: u64) -> Self {
+        Self { inner: Box::new(inner), times, before_each: None }
+    }
+
+    /// Set the hook invoked with the iteration number before each run
+    pub fn with_before_each<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(u64, &mut Environment<I>) + Send + 'static,
+    {
+        self.before_each = Some(Box::new(hook));
+        self
+    }
+}
+
+impl<I> Action<I> for RepeatAction<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for iteration in 0..self.times {
+                debug!("Running iteration {iteration} of {}", self.times);
+                if let Some(hook) = self.before_each.as_mut() {
+                    hook(iteration, env);
+                }
+                self.inner
+                    .execute(env)
+                    .await
+                    .map_err(|err| err.wrap_err(format!("Iteration {iteration} failed")))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Predicate evaluated by [`IfAction`] against the environment.
+pub type EnvironmentPredicate<I> = Box<dyn Fn(&Environment<I>) -> bool + Send>;
+
+/// Action that runs one of two branches depending on a predicate over the [`Environment`].
+///
+/// The predicate only gets read access to the environment. If it is false and there is no else
+/// branch, nothing is run.
+#[expect(missing_debug_implementations)]
+pub struct IfAction<I> {
+    /// Predicate selecting the branch
+    pub predicate: EnvironmentPredicate<I>,
+    /// Action run if the predicate is true
+    pub then_branch: Box<dyn Action<I>>,
+    /// Action run if the predicate is false
+    pub else_branch: Option<Box<dyn Action<I>>>,
+}
+
+impl<I> IfAction<I> {
+    /// Create a new `IfAction` action without an else branch
+    pub fn new<F, A>(predicate: F, then_branch: A) -> Self
+    where
+        F: Fn(&Environment<I>) -> bool + Send + 'static,
+        A: Action<I>,
+    {
+        Self {
+            predicate: Box::new(predicate),
+            then_branch: Box::new(then_branch),
+            else_branch: None,
+        }
+    }
+
+    /// Set the action run if the predicate is false
+    pub fn with_else<A: Action<I>>(mut self, else_branch: A) -> Self {
+        self.else_branch = Some(Box::new(else_branch));
+        self
+    }
+}
+
+impl<I> Action<I> for IfAction<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if (self.predicate)(env) {
+                debug!("Predicate is true, running then branch");
+                self.then_branch.execute(env).await
+            } else if let Some(else_branch) = self.else_branch.as_mut() {
+                debug!("Predicate is false, running else branch");
+                else_branch.execute(env).await
+            } else {
+                debug!("Predicate is false, skipping");
+                Ok(())
+            }
+        })
+    }
+}
+
+/// Action that asserts all nodes agree on the canonical head.
+///
+/// Nodes behind the highest head are reported separately from nodes whose head diverges at the
+/// same height. On success the agreed head is stored in [`Environment::latest_block_info`].
+#[derive(Debug, Default)]
+pub struct AssertChainTipConsistency {}
+
+impl<I> Action<I> for AssertChainTipConsistency
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if env.node_clients.is_empty() {
+                return Err(eyre::eyre!("No node clients available"));
+            }
+
+            let mut tips = Vec::with_capacity(env.node_clients.len());
+            for (idx, client) in env.node_clients.iter().enumerate() {
+                let header = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                    &client.rpc,
+                    BlockNumberOrTag::Latest,
+                    false,
+                )
+                .await?
+                .ok_or_else(|| eyre::eyre!("Node {idx}: No latest block found"))?
+                .header;
+                tips.push((header.number, header.hash));
+            }
+
+            let (highest, reference) =
+                tips.iter().copied().max_by_key(|(number, _)| *number).expect("at least one node");
+            let behind = tips
+                .iter()
+                .enumerate()
+                .filter(|(_, (number, _))| *number < highest)
+                .map(|(idx, (number, _))| format!("node {idx} ({} blocks)", highest - number))
+                .collect::<Vec<_>>();
+            let diverged = tips
+                .iter()
+                .enumerate()
+                .filter(|(_, (number, hash))| *number == highest && *hash != reference)
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+
+            if !behind.is_empty() || !diverged.is_empty() {
+                let table = tips
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (number, hash))| format!("  node {idx}: {number} {hash}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                return Err(eyre::eyre!(
+                    "Nodes disagree on the head, behind: {behind:?}, diverged at block {highest}: {diverged:?}\n{table}"
+                ));
+            }
+
+            debug!("All nodes agree on head {highest} ({reference})");
+            env.latest_block_info = Some(LatestBlockInfo { hash: reference, number: highest });
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the block a node exposes under the `finalized` tag.
+///
+/// The finalized block must have the expected hash and a number of at least `min_number`, for
+/// the checks that are set. If nothing is finalized yet the action fails, unless `min_number` is
+/// zero.
+#[derive(Debug)]
+pub struct AssertFinalizedBlock {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Expected hash of the finalized block
+    pub expected_hash: Option<B256>,
+    /// Minimum number of the finalized block
+    pub min_number: Option<u64>,
+}
+
+impl AssertFinalizedBlock {
+    /// Create a new `AssertFinalizedBlock` action
+    pub const fn new(node_idx: usize) -> Self {
+        Self { node_idx, expected_hash: None, min_number: None }
+    }
+
+    /// Set the expected hash of the finalized block
+    pub const fn with_expected_hash(mut self, expected_hash: B256) -> Self {
+        self.expected_hash = Some(expected_hash);
+        self
+    }
+
+    /// Set the minimum number of the finalized block
+    pub const fn with_min_number(mut self, min_number: u64) -> Self {
+        self.min_number = Some(min_number);
+        self
+    }
+}
+
+impl<I> Action<I> for AssertFinalizedBlock
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let finalized = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &node_client.rpc,
+                BlockNumberOrTag::Finalized,
+                false,
+            )
+            .await?;
+            let Some(finalized) = finalized else {
+                if self.min_number == Some(0) {
+                    debug!("Node {} has no finalized block yet", self.node_idx);
+                    return Ok(())
+                }
+                return Err(eyre::eyre!("Node {} has no finalized block", self.node_idx));
+            };
+            let (number, hash) = (finalized.header.number, finalized.header.hash);
+
+            if let Some(expected) = self.expected_hash {
+                if hash != expected {
+                    return Err(eyre::eyre!(
+                        "Node {}: Finalized block is {number} ({hash}), expected {expected}",
+                        self.node_idx
+                    ));
+                }
+            }
+            if let Some(min_number) = self.min_number {
+                if number < min_number {
+                    return Err(eyre::eyre!(
+                        "Node {}: Finalized block {number} is below the minimum {min_number}",
+                        self.node_idx
+                    ));
+                }
+            }
+
+            debug!("Node {}: Finalized block is {number} ({hash})", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the number of the latest block of a node.
+#[derive(Debug)]
+pub struct AssertBlockNumber {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Expected number of the latest block
+    pub expected: u64,
+}
+
+impl AssertBlockNumber {
+    /// Create a new `AssertBlockNumber` action
+    pub const fn new(node_idx: usize, expected: u64) -> Self {
+        Self { node_idx, expected }
+    }
+}
+
+impl<I> Action<I> for AssertBlockNumber
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let number: u64 =
+                EthApiClient::<Transaction, Block, Receipt, Header>::block_number(&node_client.rpc)
+                    .await?
+                    .to();
+            if number != self.expected {
+                return Err(eyre::eyre!(
+                    "Node {}: Latest block is {number}, expected {}",
+                    self.node_idx,
+                    self.expected
+                ));
+            }
+
+            debug!("Node {}: Latest block is {number}", self.node_idx);
+            Ok(())
+        })
+    }
+}
+
+/// Action that shuts a node down, relaunches it on the same datadir and brings it back to the
+/// head tracked by the [`Environment`].
+///
+/// The relaunched node is reconnected to all other nodes of the cluster. Once it answers RPC
+/// requests, the last known forkchoice state is sent to its new engine client until the node
+/// synced the blocks it is missing from its peers and accepts it as valid.
+///
+/// The [`NodeClient`](crate::testsuite::NodeClient) at `node_idx` is replaced, so any RPC, WS or
+/// engine client obtained from it before the restart must not be reused. All other [`Environment`]
+/// fields survive the restart unchanged, including the chain tracking state (`latest_block_info`,
+/// `latest_fork_choice_state`, `payload_attributes`, ...), recorded checkpoints and metrics, and
+/// any latency injected for `node_idx`.
+#[derive(Debug)]
+pub struct RestartNode<Engine> {
+    /// The node index to restart
+    pub node_idx: usize,
+    /// Maximum time to wait for the node's tasks to shut down
+    pub shutdown_timeout: Duration,
+    /// Maximum time to wait for the relaunched node's RPC endpoint to respond
+    pub rpc_timeout: Duration,
+    /// Maximum time to wait for each peer connection to be re-established
+    pub connect_timeout: Duration,
+    /// Maximum time to wait for the node to sync the blocks it is missing from its peers
+    pub sync_timeout: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> RestartNode<Engine> {
+    /// Create a new `RestartNode` action
+    pub fn new(node_idx: usize) -> Self {
+        Self {
+            node_idx,
+            shutdown_timeout: Duration::from_secs(10),
+            rpc_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(10),
+            sync_timeout: Duration::from_secs(30),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the maximum time to wait for the node to shut down
+    pub const fn with_shutdown_timeout(mut self, shutdown_timeout: Duration) -> Self {
+        self.shutdown_timeout = shutdown_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for the relaunched node's RPC endpoint to respond
+    pub const fn with_rpc_timeout(mut self, rpc_timeout: Duration) -> Self {
+        self.rpc_timeout = rpc_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for each peer connection to be re-established
+    pub const fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Set the maximum time to wait for the node to sync the blocks it is missing from its peers
+    pub const fn with_sync_timeout(mut self, sync_timeout: Duration) -> Self {
+        self.sync_timeout = sync_timeout;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for RestartNode<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            restart_node(env, self.node_idx, self.shutdown_timeout, self.connect_timeout).await?;
+
+            let deadline = Instant::now() + self.rpc_timeout;
+            let persisted_number = loop {
+                match EthApiClient::<Transaction, Block, Receipt, Header>::block_number(
+                    &env.node_clients[self.node_idx].rpc,
+                )
+                .await
+                {
+                    Ok(number) => break number.to::<u64>(),
+                    Err(e) if Instant::now() >= deadline => {
+                        return Err(eyre::eyre!(
+                            "Node {} RPC did not respond within {:?} after restart: {e}",
+                            self.node_idx,
+                            self.rpc_timeout
+                        ));
+                    }
+                    Err(_) => sleep(Duration::from_millis(100)).await,
+                }
+            };
+            debug!("Node {} restarted at persisted block {persisted_number}", self.node_idx);
+
+            // the node answers `SYNCING` until it downloaded the blocks it is missing from its
+            // peers
+            let fork_choice_state = env.latest_fork_choice_state;
+            if !fork_choice_state.head_block_hash.is_zero() {
+                let deadline = Instant::now() + self.sync_timeout;
+                loop {
+                    let status = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                        env.engine_client(self.node_idx).await?,
+                        fork_choice_state,
+                        None,
+                    )
+                    .await?
+                    .payload_status
+                    .status;
+                    if status.is_valid() {
+                        break
+                    }
+                    if !status.is_syncing() || Instant::now() >= deadline {
+                        return Err(eyre::eyre!(
+                            "Node {} did not accept the last known forkchoice {:?} after restart: \
+                             {status:?}",
+                            self.node_idx,
+                            fork_choice_state
+                        ));
+                    }
+                    sleep(Duration::from_millis(100)).await;
+                }
+            }
+
+            debug!(
+                "Node {} restarted at head {}",
+                self.node_idx, fork_choice_state.head_block_hash
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that builds the next payload on a node, applies a [`PayloadMutation`] to it and stores
+/// the result in `env.invalid_payload` for [`AssertPayloadRejected`].
+///
+/// The payload is built with the forkchoice state and attributes [`GenerateNextPayload`] would use
+/// for the next block, but it is never broadcast or made canonical. For
+/// [`PayloadMutation::ParentHash`] the parent hash is set to the grandparent of the payload, so
+/// the payload claims a known parent with the wrong number.
+///
+/// A payload generated with [`PayloadMutation::FutureTimestamp`] is only invalid at the consensus
+/// layer, the node accepts it and [`AssertPayloadRejected`] fails for it.
+#[derive(Debug)]
+pub struct GenerateInvalidPayload<Engine> {
+    /// The node index building the payload
+    pub node_idx: usize,
+    /// The mutation making the payload invalid
+    pub mutation: PayloadMutation,
+    /// Time given to the node to build the payload
+    pub build_time: Duration,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> GenerateInvalidPayload<Engine> {
+    /// Create a new `GenerateInvalidPayload` action built on node 0
+    pub fn new(mutation: PayloadMutation) -> Self {
+        Self {
+            node_idx: 0,
+            mutation,
+            build_time: Duration::from_secs(1),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Set the node index building the payload
+    pub const fn with_node(mut self, node_idx: usize) -> Self {
+        self.node_idx = node_idx;
+        self
+    }
+
+    /// Set the time given to the node to build the payload
+    pub const fn with_build_time(mut self, build_time: Duration) -> Self {
+        self.build_time = build_time;
+        self
+    }
+}
+
+impl<Engine> Action<Engine> for GenerateInvalidPayload<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+            let parent_beacon_block_root = payload_attributes
+                .parent_beacon_block_root
+                .ok_or_else(|| eyre::eyre!("No parent beacon block root in payload attributes"))?;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+
+            let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(self.node_idx).await?,
+                fork_choice_state,
+                Some(payload_attributes),
+            )
+            .await?;
+            let payload_id = fcu_result
+                .payload_id
+                .ok_or_else(|| eyre::eyre!("No payload ID returned from forkChoiceUpdated"))?;
+
+            sleep(self.build_time).await;
+
+            let envelope = EngineApiClient::<Engine>::get_payload_v3(
+                env.engine_client(self.node_idx).await?,
+                payload_id,
+            )
+            .await?;
+
+            let mut versioned_hashes = Vec::new();
+            for raw in &envelope.execution_payload.payload_inner.payload_inner.transactions {
+                let tx = TxEnvelope::decode_2718(&mut raw.as_ref())?;
+                if let Some(hashes) = tx.blob_versioned_hashes() {
+                    versioned_hashes.extend_from_slice(hashes);
+                }
+            }
+
+            let mut block = envelope
+                .execution_payload
+                .try_into_block::<reth_ethereum_primitives::TransactionSigned>()
+                .map_err(|e| eyre::eyre!("Failed to convert payload {payload_id} to block: {e}"))?;
+            let hash = match self.mutation {
+                PayloadMutation::ParentHash => {
+                    EthApiClient::<Transaction, Block, Receipt, Header>::header_by_hash(
+                        &node_client.rpc,
+                        latest_block.hash,
+                    )
+                    .await?
+                    .ok_or_else(|| eyre::eyre!("No header found for block {}", latest_block.hash))?
+                    .inner
+                    .parent_hash
+                }
+                _ => latest_block.hash,
+            };
+            self.mutation.apply(&mut block.header, hash);
+            let payload = ExecutionPayloadV3::from_block_slow(&block);
+
+            debug!(
+                "Generated invalid payload {} with mutation {:?} on node {}",
+                payload.payload_inner.payload_inner.block_hash, self.mutation, self.node_idx
+            );
+            env.invalid_payload = Some(InvalidPayload {
+                mutation: self.mutation,
+                payload,
+                versioned_hashes,
+                parent_beacon_block_root,
+            });
+            Ok(())
+        })
+    }
+}
+
+/// Action that submits the payload stored by [`GenerateInvalidPayload`] to every node with
+/// `engine_newPayloadV3` and asserts each node rejects it as `INVALID`.
+///
+/// The validation error returned by every node must contain `expected_error_substring`. The
+/// payload is kept in `env.invalid_payload`, so it can be submitted again. Payloads mutated with
+/// [`PayloadMutation::FutureTimestamp`] are not rejected, see its documentation.
+#[derive(Debug)]
+pub struct AssertPayloadRejected<Engine> {
+    /// Substring the validation error must contain
+    pub expected_error_substring: String,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> AssertPayloadRejected<Engine> {
+    /// Create a new `AssertPayloadRejected` action
+    pub fn new(expected_error_substring: impl Into<String>) -> Self {
+        Self {
+            expected_error_substring: expected_error_substring.into(),
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<Engine> Action<Engine> for AssertPayloadRejected<Engine>
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let invalid = env.invalid_payload.as_ref().ok_or_else(|| {
+                eyre::eyre!("No invalid payload found, was GenerateInvalidPayload run?")
+            })?;
+            let block_hash = invalid.payload.payload_inner.payload_inner.block_hash;
+
+            for idx in 0..env.node_clients.len() {
+                let status = EngineApiClient::<Engine>::new_payload_v3(
+                    env.engine_client(idx).await?,
+                    invalid.payload.clone(),
+                    invalid.versioned_hashes.clone(),
+                    invalid.parent_beacon_block_root,
+                )
+                .await?
+                .status;
+
+                let PayloadStatusEnum::Invalid { validation_error } = status else {
+                    return Err(eyre::eyre!(
+                        "Node {idx} did not reject payload {block_hash} with mutation {:?}: {status:?}",
+                        invalid.mutation
+                    ));
+                };
+                if !validation_error.contains(&self.expected_error_substring) {
+                    return Err(eyre::eyre!(
+                        "Node {idx} rejected payload {block_hash} with \"{validation_error}\", expected an error containing \"{}\"",
+                        self.expected_error_substring
+                    ));
+                }
+                debug!("Node {idx} rejected payload {block_hash}: {validation_error}");
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the balance of an account at a given block.
+#[derive(Debug)]
+pub struct AssertBalance {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The account to check
+    pub address: Address,
+    /// Expected balance of the account
+    pub expected: U256,
+    /// The block to check, the latest block by default
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertBalance {
+    /// Create a new `AssertBalance` action checking the latest block
+    pub const fn new(node_idx: usize, address: Address, expected: U256) -> Self {
+        Self { node_idx, address, expected, block: BlockNumberOrTag::Latest }
+    }
+
+    /// Set the block to check
+    pub const fn with_block(mut self, block: BlockNumberOrTag) -> Self {
+        self.block = block;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertBalance
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let balance = EthApiClient::<Transaction, Block, Receipt, Header>::balance(
+                &node_client.rpc,
+                self.address,
+                Some(BlockId::Number(self.block)),
+            )
+            .await?;
+
+            if balance != self.expected {
+                return Err(eyre::eyre!(
+                    "Node {}: Balance of {} at block {} is {balance}, expected {}",
+                    self.node_idx,
+                    self.address,
+                    self.block,
+                    self.expected
+                ));
+            }
+
+            debug!(
+                "Node {}: Balance of {} at block {} is {balance}",
+                self.node_idx, self.address, self.block
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the value of a contract storage slot at a given block.
+#[derive(Debug)]
+pub struct AssertStorageSlot {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The contract to check
+    pub address: Address,
+    /// The storage slot to read
+    pub slot: B256,
+    /// Expected value of the slot
+    pub expected: B256,
+    /// The block to check, the latest block by default
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertStorageSlot {
+    /// Create a new `AssertStorageSlot` action checking the latest block
+    pub const fn new(node_idx: usize, address: Address, slot: B256, expected: B256) -> Self {
+        Self { node_idx, address, slot, expected, block: BlockNumberOrTag::Latest }
+    }
+
+    /// Set the block to check
+    pub const fn with_block(mut self, block: BlockNumberOrTag) -> Self {
+        self.block = block;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertStorageSlot
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let value = EthApiClient::<Transaction, Block, Receipt, Header>::storage_at(
+                &node_client.rpc,
+                self.address,
+                JsonStorageKey::from(self.slot),
+                Some(BlockId::Number(self.block)),
+            )
+            .await?;
+
+            if value != self.expected {
+                return Err(eyre::eyre!(
+                    "Node {}: Storage slot {} of {} at block {} is {value}, expected {}",
+                    self.node_idx,
+                    self.slot,
+                    self.address,
+                    self.block,
+                    self.expected
+                ));
+            }
+
+            debug!(
+                "Node {}: Storage slot {} of {} at block {} is {value}",
+                self.node_idx, self.slot, self.address, self.block
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts whether an account has code in the latest block.
+///
+/// If `expected_hash` is set, the keccak hash of the code must match it as well.
+#[derive(Debug)]
+pub struct AssertCodeAt {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The account to check
+    pub address: Address,
+    /// Whether the account is expected to have code
+    pub expected_nonempty: bool,
+    /// Expected keccak hash of the code
+    pub expected_hash: Option<B256>,
+}
+
+impl AssertCodeAt {
+    /// Create a new `AssertCodeAt` action asserting the account has code
+    pub const fn new(node_idx: usize, address: Address) -> Self {
+        Self { node_idx, address, expected_nonempty: true, expected_hash: None }
+    }
+
+    /// Assert the account has no code instead
+    pub const fn expect_empty(mut self) -> Self {
+        self.expected_nonempty = false;
+        self
+    }
+
+    /// Set the expected keccak hash of the code
+    pub const fn with_expected_hash(mut self, expected_hash: B256) -> Self {
+        self.expected_hash = Some(expected_hash);
+        self
+    }
+}
+
+impl<I> Action<I> for AssertCodeAt
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let code = EthApiClient::<Transaction, Block, Receipt, Header>::get_code(
+                &node_client.rpc,
+                self.address,
+                Some(BlockId::latest()),
+            )
+            .await?;
+
+            if code.is_empty() == self.expected_nonempty {
+                return Err(eyre::eyre!(
+                    "Node {}: Expected {} to have {}, found {} bytes of code",
+                    self.node_idx,
+                    self.address,
+                    if self.expected_nonempty { "code" } else { "no code" },
+                    code.len()
+                ));
+            }
+            if let Some(expected) = self.expected_hash {
+                let hash = keccak256(&code);
+                if hash != expected {
+                    return Err(eyre::eyre!(
+                        "Node {}: Code hash of {} is {hash}, expected {expected}",
+                        self.node_idx,
+                        self.address
+                    ));
+                }
+            }
+
+            debug!("Node {}: {} has {} bytes of code", self.node_idx, self.address, code.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the nonce of an account at a given block.
+#[derive(Debug)]
+pub struct AssertNonce {
+    /// The node index to query
+    pub node_idx: usize,
+    /// The account to check
+    pub address: Address,
+    /// Expected nonce of the account
+    pub expected: u64,
+    /// The block to check, the latest block by default
+    pub block: BlockNumberOrTag,
+}
+
+impl AssertNonce {
+    /// Create a new `AssertNonce` action checking the latest block
+    pub const fn new(node_idx: usize, address: Address, expected: u64) -> Self {
+        Self { node_idx, address, expected, block: BlockNumberOrTag::Latest }
+    }
+
+    /// Set the block to check
+    pub const fn with_block(mut self, block: BlockNumberOrTag) -> Self {
+        self.block = block;
+        self
+    }
+}
+
+impl<I> Action<I> for AssertNonce
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let nonce = EthApiClient::<Transaction, Block, Receipt, Header>::transaction_count(
+                &node_client.rpc,
+                self.address,
+                Some(BlockId::Number(self.block)),
+            )
+            .await?;
+
+            if nonce != U256::from(self.expected) {
+                return Err(eyre::eyre!(
+                    "Node {}: Nonce of {} at block {} is {nonce}, expected {}",
+                    self.node_idx,
+                    self.address,
+                    self.block,
+                    self.expected
+                ));
+            }
+
+            debug!(
+                "Node {}: Nonce of {} at block {} is {nonce}",
+                self.node_idx, self.address, self.block
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts whether a node reports to be syncing with `eth_syncing`.
+///
+/// Any sync info reported by the node counts as syncing, reth may report the highest block equal
+/// to the current block while a sync is running.
+#[derive(Debug)]
+pub struct AssertSyncStatus {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Whether the node is expected to be synced
+    pub expect_synced: bool,
+}
+
+impl AssertSyncStatus {
+    /// Create a new `AssertSyncStatus` action
+    pub const fn new(node_idx: usize, expect_synced: bool) -> Self {
+        Self { node_idx, expect_synced }
+    }
+}
+
+impl<I> Action<I> for AssertSyncStatus
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let status =
+                EthApiClient::<Transaction, Block, Receipt, Header>::syncing(&node_client.rpc)
+                    .await?;
+
+            match (status, self.expect_synced) {
+                (SyncStatus::None, true) => {
+                    debug!("Node {} is synced", self.node_idx);
+                }
+                (SyncStatus::Info(info), true) => {
+                    return Err(eyre::eyre!(
+                        "Node {} is still syncing: current block {}, highest block {}",
+                        self.node_idx,
+                        info.current_block,
+                        info.highest_block
+                    ));
+                }
+                (SyncStatus::None, false) => {
+                    return Err(eyre::eyre!("Node {} is not syncing", self.node_idx));
+                }
+                (SyncStatus::Info(info), false) => {
+                    debug!(
+                        "Node {} is syncing: current block {}, highest block {}",
+                        self.node_idx, info.current_block, info.highest_block
+                    );
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Action that records statistics of a node's latest block in `env.block_metrics`.
+///
+/// Running it after every produced block builds up a series for [`AssertAverageGasUsed`]. A block
+/// that was already recorded by the previous run is not recorded again.
+#[derive(Debug, Default)]
+pub struct CaptureBlockMetrics {
+    /// The node index to query
+    pub node_idx: usize,
+}
+
+impl CaptureBlockMetrics {
+    /// Create a new `CaptureBlockMetrics` action
+    pub const fn new(node_idx: usize) -> Self {
+        Self { node_idx }
+    }
+}
+
+impl<I> Action<I> for CaptureBlockMetrics
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let block = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_number(
+                &node_client.rpc,
+                BlockNumberOrTag::Latest,
+                false,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("No latest block found"))?;
+            let metric = BlockMetric {
+                number: block.header.number,
+                gas_used: block.header.gas_used,
+                tx_count: block.transactions.len(),
+                timestamp: block.header.timestamp,
+            };
+
+            if env.block_metrics.last().is_some_and(|last| last.number == metric.number) {
+                debug!("Block {} already captured", metric.number);
+                return Ok(())
+            }
+
+            debug!("Captured block metric {:?}", metric);
+            env.block_metrics.push(metric);
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the average gas used by the blocks in `env.block_metrics` is within a
+/// range, both bounds inclusive.
+#[derive(Debug)]
+pub struct AssertAverageGasUsed {
+    /// Minimum average gas used
+    pub min: u64,
+    /// Maximum average gas used
+    pub max: u64,
+}
+
+impl AssertAverageGasUsed {
+    /// Create a new `AssertAverageGasUsed` action
+    pub const fn new(min: u64, max: u64) -> Self {
+        Self { min, max }
+    }
+}
+
+impl<I> Action<I> for AssertAverageGasUsed
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            if env.block_metrics.is_empty() {
+                return Err(eyre::eyre!("No block metrics captured, was CaptureBlockMetrics run?"));
+            }
+
+            let total =
+                env.block_metrics.iter().map(|metric| metric.gas_used as u128).sum::<u128>();
+            let average = (total / env.block_metrics.len() as u128) as u64;
+            if average < self.min || average > self.max {
+                return Err(eyre::eyre!(
+                    "Average gas used over {} blocks is {average}, expected between {} and {}",
+                    env.block_metrics.len(),
+                    self.min,
+                    self.max
+                ));
+            }
+
+            debug!("Average gas used over {} blocks is {average}", env.block_metrics.len());
+            Ok(())
+        })
+    }
+}
+
+/// Action that sleeps for a base duration plus a random jitter.
+///
+/// The jitter is drawn uniformly between zero and `jitter` every time the action runs.
+#[derive(Debug, Clone, Copy)]
+pub struct DelayAction {
+    /// Minimum time to sleep
+    pub base: Duration,
+    /// Maximum random time added to `base`
+    pub jitter: Option<Duration>,
+}
+
+impl DelayAction {
+    /// Create a new `DelayAction` sleeping for exactly `base`
+    pub const fn new(base: Duration) -> Self {
+        Self { base, jitter: None }
+    }
+
+    /// Set the maximum random time added to the base duration
+    pub const fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Returns the time to sleep, with a newly drawn jitter.
+    pub fn duration(&self) -> Duration {
+        let jitter = self.jitter.map_or(Duration::ZERO, |jitter| {
+            Duration::from_nanos(rand::rng().random_range(0..=jitter.as_nanos() as u64))
+        });
+        self.base + jitter
+    }
+
+    /// Sleeps for the base duration plus a random jitter.
+    pub async fn wait(&self) {
+        let duration = self.duration();
+        debug!("Sleeping for {duration:?}");
+        sleep(duration).await;
+    }
+}
+
+impl<I> Action<I> for DelayAction
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, _env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            self.wait().await;
+            Ok(())
+        })
+    }
+}
+
+/// Error returned when a node rejects a versioned engine API call because the payload is of a
+/// fork the method does not support, e.g. `engine_getPayloadV4` before Prague.
+///
+/// Tests can detect it with [`eyre::Report::downcast_ref`] and fall back to the previous version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedForkError {
+    /// The node index that rejected the call
+    pub node_idx: usize,
+    /// The engine API method that was rejected
+    pub method: &'static str,
+}
+
+impl std::fmt::Display for UnsupportedForkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Node {} does not support {} for this fork", self.node_idx, self.method)
+    }
+}
+
+impl std::error::Error for UnsupportedForkError {}
+
+/// Converts an engine API client error, mapping unsupported fork errors to
+/// [`UnsupportedForkError`].
+fn engine_error(
+    err: jsonrpsee::core::client::Error,
+    node_idx: usize,
+    method: &'static str,
+) -> eyre::Report {
+    match err {
+        jsonrpsee::core::client::Error::Call(error) if error.code() == UNSUPPORTED_FORK_CODE => {
+            UnsupportedForkError { node_idx, method }.into()
+        }
+        err => eyre::eyre!("Node {node_idx}: {method} failed: {err}"),
+    }
+}
+
+/// Action that generates the next payload like [`GenerateNextPayload`], but resolves it with
+/// `engine_getPayloadV4` so the execution requests of a Prague payload are kept.
+///
+/// The envelope is stored in `env.latest_payload_envelope_v4` for [`BroadcastNextNewPayloadV4`].
+/// If the node does not support V4 for the payload's fork the action fails with an
+/// [`UnsupportedForkError`].
+#[derive(Debug)]
+pub struct GenerateNextPayloadV4<Engine> {
+    /// Time given to the builder to build the payload
+    pub build_delay: DelayAction,
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> GenerateNextPayloadV4<Engine> {
+    /// Create a new `GenerateNextPayloadV4` action
+    pub fn new() -> Self {
+        Self { build_delay: DelayAction::new(Duration::from_secs(1)), _phantom: Default::default() }
+    }
+
+    /// Set the time given to the builder to build the payload
+    pub const fn with_build_delay(mut self, build_delay: DelayAction) -> Self {
+        self.build_delay = build_delay;
+        self
+    }
+}
+
+impl<Engine> Default for GenerateNextPayloadV4<Engine> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Engine> Action<Engine> for GenerateNextPayloadV4<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV4 = ExecutionPayloadEnvelopeV4>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let latest_block = env
+                .latest_block_info
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No latest block information available"))?;
+            let block_number = latest_block.number + 1;
+            let fork_choice_state = ForkchoiceState {
+                head_block_hash: latest_block.hash,
+                safe_block_hash: latest_block.hash,
+                finalized_block_hash: latest_block.hash,
+            };
+            let payload_attributes = env
+                .payload_attributes
+                .get(&latest_block.number)
+                .cloned()
+                .ok_or_else(|| eyre::eyre!("No payload attributes found for latest block"))?;
+            if env.node_clients.is_empty() {
+                return Err(eyre::eyre!("No node clients available"));
+            }
+
+            let fcu_result = EngineApiClient::<Engine>::fork_choice_updated_v3(
+                env.engine_client(0).await?,
+                fork_choice_state,
+                Some(payload_attributes.clone()),
+            )
+            .await?;
+            let payload_id = fcu_result
+                .payload_id
+                .ok_or_else(|| eyre::eyre!("No payload ID returned from forkChoiceUpdated"))?;
+            env.next_payload_id = Some(payload_id);
+
+            self.build_delay.wait().await;
+
+            let envelope =
+                EngineApiClient::<Engine>::get_payload_v4(env.engine_client(0).await?, payload_id)
+                    .await
+                    .map_err(|err| engine_error(err, 0, "engine_getPayloadV4"))?;
+            debug!(
+                "Built payload {payload_id} for block {block_number} with {} execution requests",
+                envelope.execution_requests.len()
+            );
+
+            env.payload_id_history.insert(block_number, payload_id);
+            env.block_producers.insert(block_number, 0);
+            env.latest_payload_built = Some(payload_attributes);
+            env.latest_payload_envelope_v4 = Some(envelope);
+            Ok(())
+        })
+    }
+}
+
+/// Action that sends the payload stored by [`GenerateNextPayloadV4`] to every node with
+/// `engine_newPayloadV4`, forwarding its execution requests, and asserts every node accepts it.
+///
+/// If a node does not support V4 for the payload's fork the action fails with an
+/// [`UnsupportedForkError`].
+#[derive(Debug)]
+pub struct BroadcastNextNewPayloadV4<Engine> {
+    /// Tracks engine type
+    _phantom: PhantomData<Engine>,
+}
+
+impl<Engine> BroadcastNextNewPayloadV4<Engine> {
+    /// Create a new `BroadcastNextNewPayloadV4` action
+    pub fn new() -> Self {
+        Self { _phantom: Default::default() }
+    }
+}
+
+impl<Engine> Default for BroadcastNextNewPayloadV4<Engine> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Engine> Action<Engine> for BroadcastNextNewPayloadV4<Engine>
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV4 = ExecutionPayloadEnvelopeV4>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let envelope = env.latest_payload_envelope_v4.clone().ok_or_else(|| {
+                eyre::eyre!("No V4 payload found, was GenerateNextPayloadV4 run?")
+            })?;
+            let payload = envelope.envelope_inner.execution_payload;
+            let block_hash = payload.payload_inner.payload_inner.block_hash;
+            let block_number = payload.payload_inner.payload_inner.block_number;
+            let parent_beacon_block_root = env
+                .latest_payload_built
+                .as_ref()
+                .and_then(|attributes| attributes.parent_beacon_block_root)
+                .ok_or_else(|| eyre::eyre!("No parent beacon block root for next new payload"))?;
+
+            let mut versioned_hashes = Vec::new();
+            for raw in &payload.payload_inner.payload_inner.transactions {
+                let tx = TxEnvelope::decode_2718(&mut raw.as_ref())?;
+                if let Some(hashes) = tx.blob_versioned_hashes() {
+                    versioned_hashes.extend_from_slice(hashes);
+                }
+            }
+
+            for idx in 0..env.node_clients.len() {
+                let status = EngineApiClient::<Engine>::new_payload_v4(
+                    env.engine_client(idx).await?,
+                    payload.clone(),
+                    versioned_hashes.clone(),
+                    parent_beacon_block_root,
+                    RequestsOrHash::Requests(envelope.execution_requests.clone()),
+                )
+                .await
+                .map_err(|err| engine_error(err, idx, "engine_newPayloadV4"))?
+                .status;
+                if !status.is_valid() {
+                    return Err(eyre::eyre!(
+                        "Node {idx}: Block {block_number} ({block_hash}) not accepted: {status:?}"
+                    ));
+                }
+            }
+
+            debug!(
+                "Block {block_number} ({block_hash}) with {} execution requests accepted by all nodes",
+                envelope.execution_requests.len()
+            );
+            env.latest_payload_executed = env.latest_payload_built.clone();
+            Ok(())
+        })
+    }
+}
+
+/// Action that fetches a block by hash and asserts it exists and matches the expected fields.
+#[derive(Debug)]
+pub struct AssertBlockByHash {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Hash of the block
+    pub hash: B256,
+    /// Expected number of transactions in the block
+    pub expected_tx_count: Option<usize>,
+    /// Expected parent hash of the block
+    pub expected_parent: Option<B256>,
+}
+
+impl AssertBlockByHash {
+    /// Create a new `AssertBlockByHash` action asserting the block exists
+    pub const fn new(node_idx: usize, hash: B256) -> Self {
+        Self { node_idx, hash, expected_tx_count: None, expected_parent: None }
+    }
+
+    /// Set the expected number of transactions in the block
+    pub const fn with_expected_tx_count(mut self, expected_tx_count: usize) -> Self {
+        self.expected_tx_count = Some(expected_tx_count);
+        self
+    }
+
+    /// Set the expected parent hash of the block
+    pub const fn with_expected_parent(mut self, expected_parent: B256) -> Self {
+        self.expected_parent = Some(expected_parent);
+        self
+    }
+}
+
+impl<I> Action<I> for AssertBlockByHash
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let block = EthApiClient::<Transaction, Block, Receipt, Header>::block_by_hash(
+                &node_client.rpc,
+                self.hash,
+                false,
+            )
+            .await?
+            .ok_or_else(|| eyre::eyre!("Node {}: Block {} not found", self.node_idx, self.hash))?;
+
+            if let Some(expected) = self.expected_tx_count {
+                let tx_count = block.transactions.len();
+                if tx_count != expected {
+                    return Err(eyre::eyre!(
+                        "Node {}: Block {} has {tx_count} transactions, expected {expected}",
+                        self.node_idx,
+                        self.hash
+                    ));
+                }
+            }
+            if let Some(expected) = self.expected_parent {
+                let parent = block.header.parent_hash;
+                if parent != expected {
+                    return Err(eyre::eyre!(
+                        "Node {}: Block {} has parent {parent}, expected {expected}",
+                        self.node_idx,
+                        self.hash
+                    ));
+                }
+            }
+
+            debug!("Node {}: Block {} ({}) matches", self.node_idx, block.header.number, self.hash);
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts the number of pending transactions in a node's pool is within bounds, both
+/// inclusive.
+#[derive(Debug)]
+pub struct AssertPendingPoolSize {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Minimum number of pending transactions
+    pub min: Option<usize>,
+    /// Maximum number of pending transactions
+    pub max: Option<usize>,
+}
+
+impl AssertPendingPoolSize {
+    /// Create a new `AssertPendingPoolSize` action without bounds
+    pub const fn new(node_idx: usize) -> Self {
+        Self { node_idx, min: None, max: None }
+    }
+
+    /// Set the minimum number of pending transactions
+    pub const fn with_min(mut self, min: usize) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the maximum number of pending transactions
+    pub const fn with_max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+}
+
+impl<I> Action<I> for AssertPendingPoolSize
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+
+            let status = TxPoolApiClient::<Transaction>::txpool_status(&node_client.rpc).await?;
+            let pending = status.pending as usize;
+
+            if self.min.is_some_and(|min| pending < min) ||
+                self.max.is_some_and(|max| pending > max)
+            {
+                return Err(eyre::eyre!(
+                    "Node {} has {} pending and {} queued transactions, expected between {:?} and {:?} pending",
+                    self.node_idx,
+                    status.pending,
+                    status.queued,
+                    self.min,
+                    self.max
+                ));
+            }
+
+            debug!(
+                "Node {} has {} pending and {} queued transactions",
+                self.node_idx, status.pending, status.queued
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that logs a summary of the chain tracking state of the [`Environment`], and optionally
+/// writes it as JSON to a file.
+///
+/// The environment is not modified, so the action can be placed anywhere in a [`Sequence`] to
+/// inspect the state at that point.
+#[derive(Debug, Default)]
+pub struct DumpEnvironment {
+    /// File to write the summary to
+    pub path: Option<PathBuf>,
+}
+
+impl DumpEnvironment {
+    /// Set the file to write the summary to, it is overwritten if it exists
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+}
+
+impl<I> Action<I> for DumpEnvironment
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut payload_id_blocks = env.payload_id_history.keys().copied().collect::<Vec<_>>();
+            payload_id_blocks.sort_unstable();
+
+            info!(
+                latest_block = ?env.latest_block_info,
+                fork_choice_state = ?env.latest_fork_choice_state,
+                ?payload_id_blocks,
+                node_clients = env.node_clients.len(),
+                last_producer_idx = ?env.last_producer_idx,
+                "Environment state"
+            );
+
+            if let Some(path) = &self.path {
+                let summary = serde_json::json!({
+                    "latest_block": env.latest_block_info.as_ref().map(|info| {
+                        serde_json::json!({ "number": info.number, "hash": info.hash })
+                    }),
+                    "fork_choice_state": env.latest_fork_choice_state,
+                    "payload_id_blocks": payload_id_blocks,
+                    "node_clients": env.node_clients.len(),
+                    "last_producer_idx": env.last_producer_idx,
+                });
+                std::fs::write(path, serde_json::to_string_pretty(&summary)?).map_err(|e| {
+                    eyre::eyre!("Failed to write environment to {}: {e}", path.display())
+                })?;
+                debug!("Wrote environment to {}", path.display());
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Action that runs an inner action and asserts it fails.
+///
+/// If `message_contains` is set, the error message of the inner action, including its causes, must
+/// contain it, ignoring case. Any changes the inner action made before failing are kept.
+#[expect(missing_debug_implementations)]
+pub struct ExpectError<I> {
+    /// Action expected to fail
+    pub inner: Box<dyn Action<I>>,
+    /// Substring the error message must contain
+    pub message_contains: Option<String>,
+}
+
+impl<I> ExpectError<I> {
+    /// Create a new `ExpectError` action accepting any error
+    pub fn new<A: Action<I>>(inner: A) -> Self {
+        Self { inner: Box::new(inner), message_contains: None }
+    }
+
+    /// Require the error message to contain the given substring, ignoring case
+    pub fn with_message_contains(mut self, message_contains: impl Into<String>) -> Self {
+        self.message_contains = Some(message_contains.into());
+        self
+    }
+}
+
+impl<I> Action<I> for ExpectError<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let err = match self.inner.execute(env).await {
+                Ok(()) => return Err(eyre::eyre!("action succeeded but was expected to fail")),
+                Err(err) => err,
+            };
+
+            let message = format!("{err:#}");
+            if let Some(expected) = &self.message_contains {
+                if !message.to_lowercase().contains(&expected.to_lowercase()) {
+                    return Err(eyre::eyre!(
+                        "action failed with \"{message}\", expected an error containing \"{expected}\""
+                    ));
+                }
+            }
+
+            debug!("Action failed as expected: {message}");
+            Ok(())
+        })
+    }
+}
+
+/// Action that asserts how many blocks the last reorg rolled back on a node.
+///
+/// The last reorg is the one recorded by [`Reorg`], [`ReplayReorgScenario`] or
+/// [`ProduceBlocksWithReorgEvery`], whichever ran last.
+///
+/// The depth is the distance between `env.pre_reorg_head` and the common ancestor of its branch
+/// with the node's current canonical chain. The blocks of the old branch are resolved by hash, so
+/// the node must still serve them.
+#[derive(Debug)]
+pub struct AssertReorgDepth {
+    /// The node index to query
+    pub node_idx: usize,
+    /// Expected reorg depth
+    pub expected_depth: u64,
+}
+
+impl AssertReorgDepth {
+    /// Create a new `AssertReorgDepth` action
+    pub const fn new(node_idx: usize, expected_depth: u64) -> Self {
+        Self { node_idx, expected_depth }
+    }
+}
+
+impl<I> Action<I> for AssertReorgDepth
+where
+    I: Send + Sync + 'static,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<I>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let node_client = env
+                .node_clients
+                .get(self.node_idx)
+                .ok_or_else(|| eyre::eyre!("Node index out of bounds: {}", self.node_idx))?;
+            let head = env
+                .pre_reorg_head
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("No pre-reorg head recorded, was Reorg run?"))?;
+
+            let depth = reorg_depth(&node_client.rpc, head, &mut vec![head.hash]).await?;
+            if depth != self.expected_depth {
+                return Err(eyre::eyre!(
+                    "Node {} reorged {depth} blocks away from {} (block {}), expected {}",
+                    self.node_idx,
+                    head.hash,
+                    head.number,
+                    self.expected_depth
+                ));
+            }
+
+            debug!(
+                "Node {} reorged {depth} blocks away from {} (block {})",
+                self.node_idx, head.hash, head.number
+            );
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_prometheus_text_samples() {
+        let body = r#"
+# HELP reth_db_table_size The size of a database table (in bytes)
+# TYPE reth_db_table_size gauge
+reth_db_table_size{table="Headers"} 4096
+reth_db_table_size{table="Plain State"} 8192.5
+reth_sync_checkpoint 12 1700000000000
+reth_malformed
+reth_not_a_number NaN_value
+"#;
+
+        let all = parse_prometheus_text(body, &[]);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[r#"reth_db_table_size{table="Headers"}"#], 4096.0);
+        assert_eq!(all[r#"reth_db_table_size{table="Plain State"}"#], 8192.5);
+        assert_eq!(all["reth_sync_checkpoint"], 12.0);
+
+        let selected = parse_prometheus_text(body, &["reth_db_table_size".to_string()]);
+        assert_eq!(selected.len(), 2);
+        assert!(!selected.contains_key("reth_sync_checkpoint"));
+    }
+
+    #[test]
+    fn metrics_delta_of_shared_series() {
+        let before = MetricsSnapshot::from([("a".to_string(), 1.0), ("b".to_string(), 5.0)]);
+        let after = MetricsSnapshot::from([("a".to_string(), 4.0), ("c".to_string(), 2.0)]);
+
+        assert_eq!(metrics_delta(&before, &after), MetricsSnapshot::from([("a".to_string(), 3.0)]));
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::testsuite::{
     actions::{AssertMineBlock, ProduceBlocks},
+    scenario::{RunScenarioFromFile, Scenario},
     setup::{NetworkSetup, Setup},
     TestBuilder,
 };
@@ -70,3 +71,33 @@ async fn test_testsuite_produce_blocks() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_testsuite_run_scenario_from_file() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let err =
+        Scenario::from_json(r#"{ "actions": [{ "action": "mine_everything" }] }"#).unwrap_err();
+    assert!(format!("{err:?}").contains("produce_blocks"));
+
+    let setup = Setup::default()
+        .with_chain_spec(Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(serde_json::from_str(include_str!("assets/genesis.json")).unwrap())
+                .cancun_activated()
+                .build(),
+        ))
+        .with_network(NetworkSetup::single_node());
+
+    let test = TestBuilder::<EthEngineTypes>::new().with_setup(setup).with_action(
+        RunScenarioFromFile::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/testsuite/assets/scenario.json"
+        )),
+    );
+
+    test.run::<EthereumNode>().await?;
+
+    Ok(())
+}
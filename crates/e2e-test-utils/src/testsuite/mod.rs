@@ -1,21 +1,33 @@
 //! Utilities for running e2e tests against a node or a network of nodes.
 
 use crate::{
-    testsuite::actions::{Action, ActionBox},
+    testsuite::actions::{Action, ActionBox, PayloadMutation},
     NodeBuilderHelper, PayloadAttributesBuilder,
 };
 use alloy_primitives::B256;
 use eyre::Result;
-use jsonrpsee::http_client::{transport::HttpBackend, HttpClient, RpcService};
+use jsonrpsee::{
+    http_client::{transport::HttpBackend, HttpClient, RpcService},
+    ws_client::WsClient,
+};
 use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_network_api::test_utils::PeersHandle;
 use reth_node_api::{NodeTypes, PayloadTypes};
 use reth_payload_builder::PayloadId;
 use reth_rpc_layer::AuthClientService;
-use setup::Setup;
-use std::{collections::HashMap, marker::PhantomData};
+use setup::{NodeLauncher, NodeLifecycle, Setup};
+use std::{
+    cmp::Ordering, collections::HashMap, marker::PhantomData, net::SocketAddr, path::PathBuf,
+    sync::Arc, time::Duration,
+};
 pub mod actions;
+pub mod scenario;
 pub mod setup;
-use alloy_rpc_types_engine::{ForkchoiceState, PayloadAttributes};
+use alloy_rpc_types_engine::{
+    ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4, ExecutionPayloadV3, ForkchoiceState,
+    PayloadAttributes,
+};
+use alloy_rpc_types_eth::Block;
 use jsonrpsee::core::middleware::layer::RpcLogger;
 
 #[cfg(test)]
@@ -26,8 +38,18 @@ mod examples;
 pub struct NodeClient {
     /// Regular JSON-RPC client
     pub rpc: HttpClient,
+    /// Websocket JSON-RPC client, used for subscriptions
+    pub ws: Option<Arc<WsClient>>,
     /// Engine API client
     pub engine: HttpClient<RpcLogger<RpcService<AuthClientService<HttpBackend>>>>,
+    /// Address of the node's Prometheus metrics server, if it has one
+    pub metrics: Option<SocketAddr>,
+    /// Handle to the node's peer manager, if it was launched by the testsuite
+    pub peers: Option<PeersHandle>,
+    /// File the node's events are logged to, if the test installed a [`setup::NodeLogLayer`]
+    pub log_path: Option<PathBuf>,
+    /// Lifecycle handle of the node, if it was launched by the testsuite
+    pub lifecycle: Option<NodeLifecycle>,
 }
 
 /// Represents the latest block information.
@@ -38,6 +60,70 @@ pub struct LatestBlockInfo {
     /// Number of the latest block
     pub number: u64,
 }
+
+/// Contiguous range of blocks fetched from a node
+#[derive(Debug, Clone)]
+pub struct CollectedBlockRange {
+    /// The fetched blocks, in ascending order by number
+    pub blocks: Vec<Block>,
+    /// Last block number that was requested
+    pub requested_end: u64,
+    /// Whether the range was cut short because it extended past the node's head
+    pub truncated: bool,
+}
+
+/// Database growth measured over a run of blocks
+#[derive(Debug, Clone)]
+pub struct StateGrowth {
+    /// Total size of the database tables before the run, in bytes
+    pub size_before: u64,
+    /// Total size of the database tables after the run, in bytes
+    pub size_after: u64,
+    /// Number of blocks added to the chain during the run
+    pub blocks: u64,
+}
+
+impl StateGrowth {
+    /// Returns the number of bytes the database grew by, zero if it shrank
+    pub const fn delta(&self) -> u64 {
+        self.size_after.saturating_sub(self.size_before)
+    }
+
+    /// Returns the average growth per block, `None` if no block was added
+    pub fn bytes_per_block(&self) -> Option<f64> {
+        (self.blocks > 0).then(|| self.delta() as f64 / self.blocks as f64)
+    }
+}
+
+/// Payload made invalid on purpose, waiting to be submitted to the nodes
+#[derive(Debug, Clone)]
+pub struct InvalidPayload {
+    /// The mutation that made the payload invalid
+    pub mutation: PayloadMutation,
+    /// The mutated payload, with its block hash recomputed
+    pub payload: ExecutionPayloadV3,
+    /// Versioned hashes of the blobs of the payload's transactions
+    pub versioned_hashes: Vec<B256>,
+    /// Parent beacon block root the payload was built with
+    pub parent_beacon_block_root: B256,
+}
+
+/// Statistics of a single block recorded by [`CaptureBlockMetrics`](actions::CaptureBlockMetrics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockMetric {
+    /// Block number
+    pub number: u64,
+    /// Gas used by the block
+    pub gas_used: u64,
+    /// Number of transactions in the block
+    pub tx_count: usize,
+    /// Block timestamp
+    pub timestamp: u64,
+}
+
+/// Metric values scraped from a node's Prometheus endpoint, indexed by series name
+pub type MetricsSnapshot = HashMap<String, f64>;
+
 /// Represents a test environment.
 #[derive(Debug)]
 pub struct Environment<I> {
@@ -65,10 +151,41 @@ pub struct Environment<I> {
     pub latest_payload_built: Option<PayloadAttributes>,
     /// Stores the most recent executed payload
     pub latest_payload_executed: Option<PayloadAttributes>,
+    /// Stores the most recent payload built with `engine_getPayloadV4`, with its execution
+    /// requests
+    pub latest_payload_envelope_v4: Option<ExecutionPayloadEnvelopeV4>,
     /// Number of slots until a block is considered safe
     pub slots_to_safe: u64,
     /// Number of slots until a block is considered finalized
     pub slots_to_finalized: u64,
+    /// Stores metrics snapshots scraped from a node, indexed by label
+    pub metrics_snapshots: HashMap<String, MetricsSnapshot>,
+    /// Launches additional nodes, installed by the setup
+    pub node_launcher: Option<NodeLauncher>,
+    /// Stores payloads built concurrently on top of the same parent, indexed by node
+    pub competing_payloads: HashMap<usize, ExecutionPayloadEnvelopeV3>,
+    /// Stores named snapshots of the chain tracking state
+    pub checkpoints: HashMap<String, EnvironmentSnapshot>,
+    /// Artificial delay applied before engine API calls to a node, indexed by node
+    pub node_latencies: HashMap<usize, Duration>,
+    /// Stores block ranges fetched from a node, indexed by label
+    pub block_ranges: HashMap<String, CollectedBlockRange>,
+    /// Stores the index of the node that produced each block, indexed by block number
+    pub block_producers: HashMap<u64, usize>,
+    /// Time the last measured transaction took to reach each node's pool, indexed by node
+    pub propagation_latencies: HashMap<usize, Duration>,
+    /// Stores database growth measurements, indexed by label
+    pub state_growth: HashMap<String, StateGrowth>,
+    /// Hashes of the transactions submitted by the actions, in submission order
+    pub last_tx_hashes: Vec<B256>,
+    /// Block numbers transactions were found to be included in, indexed by transaction hash
+    pub included_transactions: HashMap<B256, u64>,
+    /// Stores the most recent payload generated to be rejected by the nodes
+    pub invalid_payload: Option<InvalidPayload>,
+    /// Statistics of the blocks captured during the test, in capture order
+    pub block_metrics: Vec<BlockMetric>,
+    /// Head of the chain before the last reorg
+    pub pre_reorg_head: Option<LatestBlockInfo>,
 }
 
 impl<I> Default for Environment<I> {
@@ -86,12 +203,335 @@ impl<I> Default for Environment<I> {
             latest_fork_choice_state: ForkchoiceState::default(),
             latest_payload_built: None,
             latest_payload_executed: None,
+            latest_payload_envelope_v4: None,
             slots_to_safe: 0,
             slots_to_finalized: 0,
+            metrics_snapshots: HashMap::new(),
+            node_launcher: None,
+            competing_payloads: HashMap::new(),
+            checkpoints: HashMap::new(),
+            node_latencies: HashMap::new(),
+            block_ranges: HashMap::new(),
+            block_producers: HashMap::new(),
+            propagation_latencies: HashMap::new(),
+            state_growth: HashMap::new(),
+            last_tx_hashes: Vec::new(),
+            included_transactions: HashMap::new(),
+            invalid_payload: None,
+            block_metrics: Vec::new(),
+            pre_reorg_head: None,
         }
     }
 }
 
+impl<I> Environment<I> {
+    /// Returns a new [`EnvironmentBuilder`].
+    pub fn builder() -> EnvironmentBuilder<I> {
+        EnvironmentBuilder::new()
+    }
+
+    /// Returns the engine API client of the given node, after sleeping for the artificial latency
+    /// injected for the node, if any.
+    ///
+    /// Actions get the engine client through this for every engine API request, so the latency
+    /// set by [`LatencyInjector`](actions::LatencyInjector) applies to all of them.
+    pub async fn engine_client(
+        &self,
+        node_idx: usize,
+    ) -> Result<&HttpClient<RpcLogger<RpcService<AuthClientService<HttpBackend>>>>> {
+        let client = self
+            .node_clients
+            .get(node_idx)
+            .ok_or_else(|| eyre::eyre!("Node index out of bounds: {node_idx}"))?;
+        if let Some(latency) = self.node_latencies.get(&node_idx) {
+            tokio::time::sleep(*latency).await;
+        }
+        Ok(&client.engine)
+    }
+
+    /// Returns a copy of the environment for a branch of
+    /// [`ParallelActions`](actions::ParallelActions).
+    ///
+    /// The branch shares the RPC, websocket and engine clients of the nodes, but has no lifecycle
+    /// handles or node launcher. The measurement collections of the branch start empty, so that
+    /// [`merge_branch`](Self::merge_branch) only merges what the branch recorded.
+    pub(crate) fn fork_branch(&self) -> Self {
+        Self {
+            node_clients: self
+                .node_clients
+                .iter()
+                .map(|client| NodeClient {
+                    rpc: client.rpc.clone(),
+                    ws: client.ws.clone(),
+                    engine: client.engine.clone(),
+                    metrics: client.metrics,
+                    peers: client.peers.clone(),
+                    log_path: client.log_path.clone(),
+                    lifecycle: None,
+                })
+                .collect(),
+            _phantom: Default::default(),
+            latest_block_info: self.latest_block_info.clone(),
+            last_producer_idx: self.last_producer_idx,
+            payload_attributes: self.payload_attributes.clone(),
+            latest_header_time: self.latest_header_time,
+            block_timestamp_increment: self.block_timestamp_increment,
+            payload_id_history: self.payload_id_history.clone(),
+            next_payload_id: self.next_payload_id,
+            latest_fork_choice_state: self.latest_fork_choice_state,
+            latest_payload_built: self.latest_payload_built.clone(),
+            latest_payload_executed: self.latest_payload_executed.clone(),
+            latest_payload_envelope_v4: self.latest_payload_envelope_v4.clone(),
+            slots_to_safe: self.slots_to_safe,
+            slots_to_finalized: self.slots_to_finalized,
+            metrics_snapshots: HashMap::new(),
+            node_launcher: None,
+            competing_payloads: self.competing_payloads.clone(),
+            checkpoints: self.checkpoints.clone(),
+            node_latencies: self.node_latencies.clone(),
+            block_ranges: HashMap::new(),
+            block_producers: self.block_producers.clone(),
+            propagation_latencies: HashMap::new(),
+            state_growth: HashMap::new(),
+            last_tx_hashes: Vec::new(),
+            included_transactions: HashMap::new(),
+            invalid_payload: self.invalid_payload.clone(),
+            block_metrics: Vec::new(),
+            pre_reorg_head: self.pre_reorg_head.clone(),
+        }
+    }
+
+    /// Merges the results recorded by a branch of [`ParallelActions`](actions::ParallelActions)
+    /// back into the environment.
+    ///
+    /// Only the measurements recorded by the branch are merged, all other changes of the branch
+    /// are discarded.
+    pub(crate) fn merge_branch(&mut self, branch: Self) {
+        self.metrics_snapshots.extend(branch.metrics_snapshots);
+        self.block_ranges.extend(branch.block_ranges);
+        self.propagation_latencies.extend(branch.propagation_latencies);
+        self.state_growth.extend(branch.state_growth);
+        self.last_tx_hashes.extend(branch.last_tx_hashes);
+        self.included_transactions.extend(branch.included_transactions);
+        self.block_metrics.extend(branch.block_metrics);
+    }
+
+    /// Returns a snapshot of the chain tracking state of the environment.
+    pub fn snapshot(&self) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            latest_block_info: self.latest_block_info.clone(),
+            last_producer_idx: self.last_producer_idx,
+            latest_header_time: self.latest_header_time,
+            payload_id_history: self.payload_id_history.clone(),
+            next_payload_id: self.next_payload_id,
+            latest_fork_choice_state: self.latest_fork_choice_state,
+        }
+    }
+
+    /// Restores the chain tracking state of the environment from a snapshot.
+    ///
+    /// Bookkeeping of blocks above the snapshot's latest block is dropped, as are built payloads
+    /// that are not part of the snapshot. The chains of the nodes are left untouched.
+    pub fn restore(&mut self, snapshot: &EnvironmentSnapshot) {
+        let latest_number = snapshot.latest_block_info.as_ref().map(|info| info.number);
+        self.latest_block_info = snapshot.latest_block_info.clone();
+        self.last_producer_idx = snapshot.last_producer_idx;
+        self.latest_header_time = snapshot.latest_header_time;
+        self.payload_id_history = snapshot.payload_id_history.clone();
+        self.next_payload_id = snapshot.next_payload_id;
+        self.latest_fork_choice_state = snapshot.latest_fork_choice_state;
+        self.payload_attributes.retain(|number, _| Some(*number) <= latest_number);
+        self.block_producers.retain(|number, _| Some(*number) <= latest_number);
+        self.latest_payload_built = None;
+        self.latest_payload_executed = None;
+        self.latest_payload_envelope_v4 = None;
+        self.competing_payloads.clear();
+        self.invalid_payload = None;
+    }
+
+    /// Clears all state tracking the chain of the nodes, as if no block had been produced yet.
+    ///
+    /// Configuration such as the timestamp increment and injected latencies is kept.
+    pub fn reset_chain_tracking(&mut self) {
+        self.latest_block_info = None;
+        self.last_producer_idx = None;
+        self.payload_attributes.clear();
+        self.latest_header_time = 0;
+        self.payload_id_history.clear();
+        self.next_payload_id = None;
+        self.latest_fork_choice_state = ForkchoiceState::default();
+        self.latest_payload_built = None;
+        self.latest_payload_executed = None;
+        self.latest_payload_envelope_v4 = None;
+        self.competing_payloads.clear();
+        self.invalid_payload = None;
+        self.pre_reorg_head = None;
+        self.checkpoints.clear();
+        self.block_producers.clear();
+    }
+
+    /// Removes a node from the environment and returns its client.
+    ///
+    /// Indices of the nodes after the removed one shift down by one, and all state the environment
+    /// keeps per node index is reindexed to match. State kept for the removed node is dropped, and
+    /// if it was the last block producer, `last_producer_idx` is cleared.
+    pub fn remove_node(&mut self, node_idx: usize) -> Result<NodeClient> {
+        if node_idx >= self.node_clients.len() {
+            return Err(eyre::eyre!("Node index out of bounds: {node_idx}"));
+        }
+        let client = self.node_clients.remove(node_idx);
+
+        let reindex = move |idx: usize| match idx.cmp(&node_idx) {
+            Ordering::Less => Some(idx),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(idx - 1),
+        };
+        self.last_producer_idx = self.last_producer_idx.and_then(reindex);
+        for checkpoint in self.checkpoints.values_mut() {
+            checkpoint.last_producer_idx = checkpoint.last_producer_idx.and_then(reindex);
+        }
+        self.block_producers = std::mem::take(&mut self.block_producers)
+            .into_iter()
+            .filter_map(|(number, idx)| Some((number, reindex(idx)?)))
+            .collect();
+        reindex_keys(&mut self.competing_payloads, reindex);
+        reindex_keys(&mut self.node_latencies, reindex);
+        reindex_keys(&mut self.propagation_latencies, reindex);
+
+        Ok(client)
+    }
+
+    /// Returns the index of the node that produced the given block, if known.
+    ///
+    /// Producers are recorded by the actions producing blocks. If a block was reorged, this is
+    /// the producer of the block that replaced it.
+    pub fn block_producer(&self, block_number: u64) -> Option<usize> {
+        self.block_producers.get(&block_number).copied()
+    }
+}
+
+/// Moves the entries of a map indexed by node to the new node indices, dropping the entries
+/// `reindex` maps to `None`.
+fn reindex_keys<V>(map: &mut HashMap<usize, V>, reindex: impl Fn(usize) -> Option<usize>) {
+    *map = std::mem::take(map)
+        .into_iter()
+        .filter_map(|(idx, value)| Some((reindex(idx)?, value)))
+        .collect();
+}
+
+/// Builder for an [`Environment`] that validates its configuration.
+#[derive(Debug)]
+pub struct EnvironmentBuilder<I> {
+    node_clients: Vec<NodeClient>,
+    node_launcher: Option<NodeLauncher>,
+    block_timestamp_increment: u64,
+    slots_to_safe: u64,
+    slots_to_finalized: u64,
+    _phantom: PhantomData<I>,
+}
+
+impl<I> Default for EnvironmentBuilder<I> {
+    fn default() -> Self {
+        Self {
+            node_clients: vec![],
+            node_launcher: None,
+            block_timestamp_increment: 2,
+            slots_to_safe: 0,
+            slots_to_finalized: 0,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<I> EnvironmentBuilder<I> {
+    /// Create a new `EnvironmentBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the node clients
+    pub fn with_node_clients(mut self, node_clients: Vec<NodeClient>) -> Self {
+        self.node_clients = node_clients;
+        self
+    }
+
+    /// Add a node client
+    pub fn with_node_client(mut self, node_client: NodeClient) -> Self {
+        self.node_clients.push(node_client);
+        self
+    }
+
+    /// Set the node launcher
+    pub fn with_node_launcher(mut self, node_launcher: NodeLauncher) -> Self {
+        self.node_launcher = Some(node_launcher);
+        self
+    }
+
+    /// Set the increment for block timestamps
+    pub const fn with_block_timestamp_increment(mut self, block_timestamp_increment: u64) -> Self {
+        self.block_timestamp_increment = block_timestamp_increment;
+        self
+    }
+
+    /// Set the number of slots until a block is considered safe
+    pub const fn with_slots_to_safe(mut self, slots_to_safe: u64) -> Self {
+        self.slots_to_safe = slots_to_safe;
+        self
+    }
+
+    /// Set the number of slots until a block is considered finalized
+    pub const fn with_slots_to_finalized(mut self, slots_to_finalized: u64) -> Self {
+        self.slots_to_finalized = slots_to_finalized;
+        self
+    }
+
+    /// Validates the configuration and builds the [`Environment`].
+    pub fn build(self) -> Result<Environment<I>> {
+        if self.node_clients.is_empty() {
+            return Err(eyre::eyre!("Environment requires at least one node client"));
+        }
+        if self.block_timestamp_increment == 0 {
+            return Err(eyre::eyre!("Block timestamp increment must be greater than zero"));
+        }
+        if self.slots_to_finalized < self.slots_to_safe {
+            return Err(eyre::eyre!(
+                "Slots to finalized ({}) must not be less than slots to safe ({})",
+                self.slots_to_finalized,
+                self.slots_to_safe
+            ));
+        }
+
+        Ok(Environment {
+            node_clients: self.node_clients,
+            node_launcher: self.node_launcher,
+            block_timestamp_increment: self.block_timestamp_increment,
+            slots_to_safe: self.slots_to_safe,
+            slots_to_finalized: self.slots_to_finalized,
+            ..Default::default()
+        })
+    }
+}
+
+/// Snapshot of the chain tracking state of an [`Environment`].
+///
+/// This only covers the in-memory bookkeeping of the environment, the chains of the nodes are not
+/// part of the snapshot.
+#[derive(Debug, Clone)]
+pub struct EnvironmentSnapshot {
+    /// Latest block information
+    pub latest_block_info: Option<LatestBlockInfo>,
+    /// Last producer index
+    pub last_producer_idx: Option<usize>,
+    /// Latest block header timestamp
+    pub latest_header_time: u64,
+    /// Payload IDs returned by block producers, indexed by block number
+    pub payload_id_history: HashMap<u64, PayloadId>,
+    /// Next expected payload ID
+    pub next_payload_id: Option<PayloadId>,
+    /// Latest fork choice state
+    pub latest_fork_choice_state: ForkchoiceState,
+}
+
 /// Builder for creating test scenarios
 #[expect(missing_debug_implementations)]
 #[derive(Default)]
@@ -152,10 +592,12 @@ impl<I: 'static> TestBuilder<I> {
             action.execute(&mut self.env).await?;
         }
 
-        // explicitly drop the setup to shutdown the nodes
-        // after all actions have completed
         drop(setup);
 
+        // explicitly drop the environment to shutdown the nodes
+        // after all actions have completed
+        drop(self.env);
+
         Ok(())
     }
 }
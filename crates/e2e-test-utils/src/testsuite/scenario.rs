@@ -0,0 +1,271 @@
+//! Scenarios described declaratively in JSON files.
+//!
+//! A scenario file lists actions by name along with their parameters:
+//!
+//! ```json
+//! {
+//!     "actions": [
+//!         { "action": "produce_blocks", "num_blocks": 5 },
+//!         { "action": "checkpoint", "name": "after-setup" },
+//!         { "action": "latency_injector", "node_idx": 1, "latency_ms": 200 },
+//!         { "action": "assert_difficulty_zero", "node_idx": 0, "block": "latest" }
+//!     ]
+//! }
+//! ```
+//!
+//! Only the actions of [`ScenarioAction`] are supported, unknown action names are rejected with
+//! an error listing the supported ones.
+
+use crate::testsuite::{
+    actions::{
+        Action, ActionBox, AddNode, AssertBlockHashConsistency, AssertBlockNumber,
+        AssertDifficultyZero, AssertEngineGetPayloadUnknownId, AssertNoPendingTransactions,
+        AssertPayloadIdStable, Checkpoint, LatencyInjector, ProduceBlockWithSpecificProducer,
+        ProduceBlocks, ProduceBlocksWithReorgEvery, RemoveNode, RestartClusterFromGenesis,
+        RestoreCheckpoint, Sequence, ShutdownAndRestartNode, WaitForPayloadBuildTime,
+    },
+    Environment,
+};
+use alloy_eips::BlockNumberOrTag;
+use alloy_rpc_types_engine::{ExecutionPayloadEnvelopeV3, PayloadAttributes};
+use eyre::{Result, WrapErr};
+use futures_util::future::BoxFuture;
+use reth_node_api::{EngineTypes, PayloadTypes};
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tracing::debug;
+
+/// A list of actions loaded from a scenario file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Actions of the scenario, in execution order
+    pub actions: Vec<ScenarioAction>,
+}
+
+impl Scenario {
+    /// Parses a scenario from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).wrap_err("Failed to parse scenario")
+    }
+
+    /// Reads and parses a scenario from a JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read scenario file {}", path.display()))?;
+        Self::from_json(&json).wrap_err_with(|| format!("Invalid scenario file {}", path.display()))
+    }
+
+    /// Builds the actions of the scenario.
+    pub fn into_actions<Engine>(self) -> Vec<ActionBox<Engine>>
+    where
+        Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+            + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+    {
+        self.actions.into_iter().map(|action| ActionBox::from(action.into_action())).collect()
+    }
+
+    /// Builds a [`Sequence`] running the actions of the scenario in order.
+    pub fn into_sequence<Engine>(self) -> Sequence<Engine>
+    where
+        Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+            + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+    {
+        Sequence::new(self.actions.into_iter().map(ScenarioAction::into_action).collect())
+    }
+}
+
+/// An action of a [`Scenario`], tagged by its name in the `action` field.
+///
+/// Durations are given in milliseconds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ScenarioAction {
+    /// [`ProduceBlocks`]
+    ProduceBlocks {
+        /// Number of blocks to produce
+        num_blocks: u64,
+    },
+    /// [`ProduceBlockWithSpecificProducer`]
+    ProduceBlockWithSpecificProducer {
+        /// The node index building the block
+        producer_idx: usize,
+    },
+    /// [`ProduceBlocksWithReorgEvery`]
+    ProduceBlocksWithReorgEvery {
+        /// Number of blocks to produce
+        num_blocks: u64,
+        /// Interval of blocks between reorgs
+        reorg_every: u64,
+    },
+    /// [`Checkpoint`]
+    Checkpoint {
+        /// Name of the checkpoint
+        name: String,
+    },
+    /// [`RestoreCheckpoint`]
+    RestoreCheckpoint {
+        /// Name of the checkpoint
+        name: String,
+    },
+    /// [`LatencyInjector`]
+    LatencyInjector {
+        /// The node index to delay
+        node_idx: usize,
+        /// Latency in milliseconds, zero removes it
+        latency_ms: u64,
+    },
+    /// [`AddNode`]
+    AddNode {
+        /// Maximum time to wait for each peer connection
+        connect_timeout_ms: u64,
+    },
+    /// [`RemoveNode`]
+    RemoveNode {
+        /// The node index to remove
+        node_idx: usize,
+    },
+    /// [`ShutdownAndRestartNode`]
+    ShutdownAndRestartNode {
+        /// The node index to restart
+        node_idx: usize,
+    },
+    /// [`RestartClusterFromGenesis`]
+    RestartClusterFromGenesis,
+    /// [`WaitForPayloadBuildTime`]
+    WaitForPayloadBuildTime {
+        /// The node index building the payload
+        node_idx: usize,
+        /// Maximum build time in milliseconds
+        budget_ms: u64,
+    },
+    /// [`AssertNoPendingTransactions`]
+    AssertNoPendingTransactions {
+        /// The node index to query
+        node_idx: usize,
+    },
+    /// [`AssertDifficultyZero`]
+    AssertDifficultyZero {
+        /// The node index to query
+        node_idx: usize,
+        /// The block to check
+        block: BlockNumberOrTag,
+    },
+    /// [`AssertBlockNumber`]
+    AssertBlockNumber {
+        /// The node index to query
+        node_idx: usize,
+        /// Expected number of the latest block
+        expected: u64,
+    },
+    /// [`AssertBlockHashConsistency`]
+    AssertBlockHashConsistency {
+        /// The node index to query
+        node_idx: usize,
+        /// The block to check
+        block: BlockNumberOrTag,
+    },
+    /// [`AssertPayloadIdStable`]
+    AssertPayloadIdStable {
+        /// The node index to query
+        node_idx: usize,
+    },
+    /// [`AssertEngineGetPayloadUnknownId`]
+    AssertEngineGetPayloadUnknownId {
+        /// The node index to query
+        node_idx: usize,
+    },
+}
+
+impl ScenarioAction {
+    /// Builds the action.
+    pub fn into_action<Engine>(self) -> Box<dyn Action<Engine>>
+    where
+        Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+            + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+    {
+        match self {
+            Self::ProduceBlocks { num_blocks } => {
+                Box::new(ProduceBlocks::<Engine>::new(num_blocks))
+            }
+            Self::ProduceBlockWithSpecificProducer { producer_idx } => {
+                Box::new(ProduceBlockWithSpecificProducer::<Engine>::new(producer_idx))
+            }
+            Self::ProduceBlocksWithReorgEvery { num_blocks, reorg_every } => {
+                Box::new(ProduceBlocksWithReorgEvery::<Engine>::new(num_blocks, reorg_every))
+            }
+            Self::Checkpoint { name } => Box::new(Checkpoint::new(name)),
+            Self::RestoreCheckpoint { name } => Box::new(RestoreCheckpoint::new(name)),
+            Self::LatencyInjector { node_idx, latency_ms } => {
+                Box::new(LatencyInjector::new(node_idx, Duration::from_millis(latency_ms)))
+            }
+            Self::AddNode { connect_timeout_ms } => {
+                Box::new(AddNode::new(Duration::from_millis(connect_timeout_ms)))
+            }
+            Self::RemoveNode { node_idx } => Box::new(RemoveNode::new(node_idx)),
+            Self::ShutdownAndRestartNode { node_idx } => {
+                Box::new(ShutdownAndRestartNode::new(node_idx))
+            }
+            Self::RestartClusterFromGenesis => Box::new(RestartClusterFromGenesis::new()),
+            Self::WaitForPayloadBuildTime { node_idx, budget_ms } => Box::new(
+                WaitForPayloadBuildTime::new(Duration::from_millis(budget_ms)).with_node(node_idx),
+            ),
+            Self::AssertNoPendingTransactions { node_idx } => {
+                Box::new(AssertNoPendingTransactions::new(node_idx))
+            }
+            Self::AssertDifficultyZero { node_idx, block } => {
+                Box::new(AssertDifficultyZero::new(node_idx, block))
+            }
+            Self::AssertBlockNumber { node_idx, expected } => {
+                Box::new(AssertBlockNumber::new(node_idx, expected))
+            }
+            Self::AssertBlockHashConsistency { node_idx, block } => {
+                Box::new(AssertBlockHashConsistency::new(node_idx, block))
+            }
+            Self::AssertPayloadIdStable { node_idx } => {
+                Box::new(AssertPayloadIdStable::<Engine>::new(node_idx))
+            }
+            Self::AssertEngineGetPayloadUnknownId { node_idx } => {
+                Box::new(AssertEngineGetPayloadUnknownId::<Engine>::new(node_idx))
+            }
+        }
+    }
+}
+
+/// Action that loads a [`Scenario`] from a JSON file and runs its actions in order.
+///
+/// The file is read when the action executes, so an invalid file fails the test at that point.
+#[derive(Debug)]
+pub struct RunScenarioFromFile {
+    /// Path of the scenario file
+    pub path: PathBuf,
+}
+
+impl RunScenarioFromFile {
+    /// Create a new `RunScenarioFromFile` action
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl<Engine> Action<Engine> for RunScenarioFromFile
+where
+    Engine: EngineTypes<ExecutionPayloadEnvelopeV3 = ExecutionPayloadEnvelopeV3>
+        + PayloadTypes<PayloadAttributes = PayloadAttributes>,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scenario = Scenario::from_file(&self.path)?;
+            debug!(
+                "Running {} actions from scenario {}",
+                scenario.actions.len(),
+                self.path.display()
+            );
+            scenario.into_sequence().execute(env).await
+        })
+    }
+}
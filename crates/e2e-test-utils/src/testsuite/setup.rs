@@ -1,25 +1,55 @@
 //! Test setup utilities for configuring the initial state.
 
-use crate::{setup_engine, testsuite::Environment, NodeBuilderHelper, PayloadAttributesBuilder};
+use crate::{
+    node::NodeTestContext,
+    testsuite::{Environment, NodeClient},
+    NodeBuilderHelper, NodeHelperType, PayloadAttributesBuilder, TmpDB,
+};
 use alloy_eips::BlockNumberOrTag;
 use alloy_primitives::B256;
 use alloy_rpc_types_engine::PayloadAttributes;
 use alloy_rpc_types_eth::{Block as RpcBlock, Header, Receipt, Transaction};
 use eyre::{eyre, Result};
+use futures_util::future::BoxFuture;
 use reth_chainspec::ChainSpec;
+use reth_db::test_utils::{create_test_rw_db_with_path, tempdir_path};
 use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_engine_primitives::TreeConfig;
 use reth_ethereum_primitives::Block;
+use reth_network_api::test_utils::PeersHandleProvider;
+use reth_network_peers::NodeRecord;
 use reth_node_api::{NodeTypes, PayloadTypes};
-use reth_node_core::primitives::RecoveredBlock;
+use reth_node_builder::{EngineNodeLauncher, Node, NodeBuilder, NodeConfig, NodeHandle};
+use reth_node_core::{
+    args::{DatadirArgs, DiscoveryArgs, NetworkArgs, PayloadBuilderArgs, RpcServerArgs},
+    primitives::RecoveredBlock,
+};
 use reth_payload_builder::EthPayloadBuilderAttributes;
-use reth_rpc_api::clients::EthApiClient;
+use reth_provider::providers::BlockchainProvider;
+use reth_rpc_api::clients::{AdminApiClient, EthApiClient};
+use reth_rpc_server_types::RpcModuleSelection;
+use reth_tasks::TaskManager;
 use revm::state::EvmState;
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::{self, Write as _},
+    fs::File,
+    io::Write as _,
+    marker::PhantomData,
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 use tokio::{
-    sync::mpsc,
-    time::{sleep, Duration},
+    sync::oneshot,
+    time::{sleep, Duration, Instant},
 };
-use tracing::{debug, error};
+use tracing::{
+    debug, error,
+    field::{Field, Visit},
+    span, Event, Instrument, Level, Span, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
 
 /// Configuration for setting upa test environment
 #[derive(Debug)]
@@ -34,10 +64,15 @@ pub struct Setup<I> {
     pub state: Option<EvmState>,
     /// Network configuration
     pub network: NetworkSetup,
-    /// Shutdown channel to stop nodes when setup is dropped
-    shutdown_tx: Option<mpsc::Sender<()>>,
     /// Is this setup in dev mode
     pub is_dev: bool,
+    /// Gas limit targeted by the payload builders of the nodes, the chain's default if unset
+    pub builder_gas_limit: Option<u64>,
+    /// Whether the nodes serve Prometheus metrics
+    pub metrics: bool,
+    /// Number of canonical blocks the nodes keep in memory before persisting them, the engine's
+    /// default if unset
+    pub persistence_threshold: Option<u64>,
     /// Tracks instance generic.
     _phantom: PhantomData<I>,
 }
@@ -50,22 +85,15 @@ impl<I> Default for Setup<I> {
             blocks: Vec::new(),
             state: None,
             network: NetworkSetup::default(),
-            shutdown_tx: None,
             is_dev: true,
+            builder_gas_limit: None,
+            metrics: false,
+            persistence_threshold: None,
             _phantom: Default::default(),
         }
     }
 }
 
-impl<I> Drop for Setup<I> {
-    fn drop(&mut self) {
-        // Send shutdown signal if the channel exists
-        if let Some(tx) = self.shutdown_tx.take() {
-            let _ = tx.try_send(());
-        }
-    }
-}
-
 impl<I> Setup<I> {
     /// Create a new setup with default values
     pub fn new() -> Self {
@@ -114,6 +142,31 @@ impl<I> Setup<I> {
         self
     }
 
+    /// Set the gas limit targeted by the payload builders of the nodes
+    pub const fn with_builder_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.builder_gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Enable the Prometheus metrics server of the nodes, required by the metrics actions.
+    ///
+    /// Every node serves its metrics on a local port that is picked as unused right before the
+    /// node is launched.
+    pub const fn with_metrics(mut self) -> Self {
+        self.metrics = true;
+        self
+    }
+
+    /// Set the number of canonical blocks the nodes keep in memory before persisting them.
+    ///
+    /// With a threshold of `0` every canonical block is persisted right away, so a node restarted
+    /// with [`ShutdownAndRestartNode`](crate::testsuite::actions::ShutdownAndRestartNode) keeps its
+    /// head.
+    pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
+        self.persistence_threshold = Some(persistence_threshold);
+        self
+    }
+
     /// Apply the setup to the environment
     pub async fn apply<N>(&mut self, env: &mut Environment<I>) -> Result<()>
     where
@@ -124,62 +177,46 @@ impl<I> Setup<I> {
     {
         let chain_spec =
             self.chain_spec.clone().ok_or_else(|| eyre!("Chain specification is required"))?;
+        let chain_spec = Arc::<N::ChainSpec>::new((*chain_spec).clone().into());
 
-        let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
-
-        self.shutdown_tx = Some(shutdown_tx);
-
-        let is_dev = self.is_dev;
-        let node_count = self.network.node_count;
-
-        let attributes_generator = move |timestamp| {
-            let attributes = PayloadAttributes {
-                timestamp,
-                prev_randao: B256::ZERO,
-                suggested_fee_recipient: alloy_primitives::Address::ZERO,
-                withdrawals: Some(vec![]),
-                parent_beacon_block_root: Some(B256::ZERO),
-            };
-            <<N as NodeTypes>::Payload as PayloadTypes>::PayloadBuilderAttributes::from(
-                EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
-            )
+        let options = LaunchOptions {
+            is_dev: self.is_dev,
+            builder_gas_limit: self.builder_gas_limit,
+            metrics: self.metrics,
+            persistence_threshold: self.persistence_threshold,
         };
+        let node_count = self.network.node_count;
 
-        let result = setup_engine::<N>(
-            node_count,
-            Arc::<N::ChainSpec>::new((*chain_spec).clone().into()),
-            is_dev,
-            attributes_generator,
-        )
-        .await;
-
-        let mut node_clients = Vec::new();
-        match result {
-            Ok((nodes, executor, _wallet)) => {
-                // create HTTP clients for each node's RPC and Engine API endpoints
-                for node in &nodes {
-                    let rpc = node
-                        .rpc_client()
-                        .ok_or_else(|| eyre!("Failed to create HTTP RPC client for node"))?;
-                    let engine = node.engine_api_client();
-
-                    node_clients.push(crate::testsuite::NodeClient { rpc, engine });
+        let mut nodes = Vec::with_capacity(node_count);
+        for idx in 0..node_count {
+            let launched = launch_node::<N>(idx, chain_spec.clone(), options, None).await;
+            let (mut node, tasks, storage) = match launched {
+                Ok(launched) => launched,
+                Err(e) => {
+                    error!("Failed to setup nodes: {}", e);
+                    return Err(eyre!("Failed to setup nodes: {}", e));
                 }
+            };
 
-                // spawn a separate task just to handle the shutdown
-                tokio::spawn(async move {
-                    // keep nodes and executor in scope to ensure they're not dropped
-                    let _nodes = nodes;
-                    let _executor = executor;
-                    // Wait for shutdown signal
-                    let _ = shutdown_rx.recv().await;
-                    // nodes and executor will be dropped here when the test completes
-                });
+            // Connect each node in a chain.
+            if let Some((previous_node, _, _)) = nodes.last_mut() {
+                previous_node.connect(&mut node).await;
             }
-            Err(e) => {
-                error!("Failed to setup nodes: {}", e);
-                return Err(eyre!("Failed to setup nodes: {}", e));
+
+            // Connect last node with the first if there are more than two
+            if idx + 1 == node_count && node_count > 2 {
+                if let Some((first_node, _, _)) = nodes.first_mut() {
+                    node.connect(first_node).await;
+                }
             }
+
+            nodes.push((node, tasks, storage));
+        }
+
+        // hand each node over to its own task so it can be shut down individually
+        let mut node_clients = Vec::with_capacity(nodes.len());
+        for (node, tasks, storage) in nodes {
+            node_clients.push(spawn_node::<N>(node, tasks, storage).await?);
         }
 
         if node_clients.is_empty() {
@@ -188,42 +225,450 @@ impl<I> Setup<I> {
 
         // wait for all nodes to be ready to accept RPC requests before proceeding
         for (idx, client) in node_clients.iter().enumerate() {
-            let mut retry_count = 0;
-            const MAX_RETRIES: usize = 5;
-            let mut last_error = None;
-
-            while retry_count < MAX_RETRIES {
-                match EthApiClient::<Transaction, RpcBlock, Receipt, Header>::block_by_number(
-                    &client.rpc,
-                    BlockNumberOrTag::Latest,
-                    false,
-                )
-                .await
-                {
-                    Ok(_) => {
-                        debug!("Node {idx} RPC endpoint is ready");
-                        break;
-                    }
-                    Err(e) => {
-                        last_error = Some(e);
-                        retry_count += 1;
-                        debug!(
-                            "Node {idx} RPC endpoint not ready, retry {retry_count}/{MAX_RETRIES}"
-                        );
-                        sleep(Duration::from_millis(500)).await;
+            wait_for_node_ready(idx, client).await?;
+        }
+
+        env.node_launcher = Some(NodeLauncher::new(move |idx, storage| {
+            let chain_spec = chain_spec.clone();
+            Box::pin(async move {
+                let (node, tasks, storage) =
+                    launch_node::<N>(idx, chain_spec, options, storage).await?;
+                let client = spawn_node::<N>(node, tasks, storage).await?;
+                wait_for_node_ready(idx, &client).await?;
+                Ok(client)
+            })
+        }));
+        env.node_clients = node_clients;
+
+        // TODO: For each block in self.blocks, replay it on the node
+
+        Ok(())
+    }
+}
+
+/// Storage of a node launched by the testsuite.
+///
+/// Holding on to the database keeps the node's datadir around after the node was shut down, so it
+/// can be relaunched on the same datadir.
+#[derive(Debug, Clone)]
+pub struct NodeStorage {
+    /// Database of the node
+    pub db: TmpDB,
+    /// Data directory of the node
+    pub datadir: PathBuf,
+    /// File the node's events are logged to, if the test installed a [`NodeLogLayer`]
+    pub log_path: Option<PathBuf>,
+}
+
+/// Handle controlling the lifecycle of a node launched by the testsuite.
+///
+/// Dropping the handle shuts the node down without waiting for its tasks.
+#[derive(Debug)]
+pub struct NodeLifecycle {
+    /// Storage of the node
+    pub storage: NodeStorage,
+    /// Sends the shutdown signal together with the graceful shutdown timeout
+    shutdown_tx: Option<oneshot::Sender<(Duration, oneshot::Sender<bool>)>>,
+    /// Errors of the critical tasks that panicked while the node was running
+    panicked_tasks: Arc<Mutex<Vec<String>>>,
+}
+
+impl NodeLifecycle {
+    /// Returns `true` if the node has not been shut down yet.
+    pub const fn is_running(&self) -> bool {
+        self.shutdown_tx.is_some()
+    }
+
+    /// Shuts the node down and waits for its tasks to finish.
+    ///
+    /// Returns `true` if all tasks exited within the timeout, `false` if the node had to be
+    /// forcefully stopped.
+    pub async fn shutdown(&mut self, timeout: Duration) -> Result<bool> {
+        let shutdown_tx =
+            self.shutdown_tx.take().ok_or_else(|| eyre!("Node has already been shut down"))?;
+        let (exited_tx, exited_rx) = oneshot::channel();
+        shutdown_tx.send((timeout, exited_tx)).map_err(|_| eyre!("Node task is not running"))?;
+        Ok(exited_rx.await?)
+    }
+
+    /// Returns the errors of the critical tasks that panicked while the node was running.
+    pub fn panicked_tasks(&self) -> Vec<String> {
+        self.panicked_tasks.lock().map(|panics| panics.clone()).unwrap_or_default()
+    }
+}
+
+/// Launches a new node, reusing the given storage if any, and returns its clients once it is
+/// ready to accept RPC requests.
+///
+/// The launcher is installed into the [`Environment`] by [`Setup::apply`], so actions can start
+/// nodes without knowing the node type. Launched nodes are not connected to any peers.
+#[derive(Clone)]
+pub struct NodeLauncher(
+    Arc<dyn Fn(usize, Option<NodeStorage>) -> BoxFuture<'static, Result<NodeClient>> + Send + Sync>,
+);
+
+impl NodeLauncher {
+    /// Create a new `NodeLauncher` from the given launch function
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(usize, Option<NodeStorage>) -> BoxFuture<'static, Result<NodeClient>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Launches a node with the given index, used for logging only.
+    ///
+    /// If `storage` is set the node is launched on that database and datadir, otherwise a fresh
+    /// temporary datadir is used.
+    pub async fn launch(&self, idx: usize, storage: Option<NodeStorage>) -> Result<NodeClient> {
+        (self.0)(idx, storage).await
+    }
+}
+
+impl fmt::Debug for NodeLauncher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeLauncher").finish_non_exhaustive()
+    }
+}
+
+/// Options of the [`Setup`] every node is launched with.
+#[derive(Debug, Clone, Copy)]
+struct LaunchOptions {
+    /// Whether the nodes run in dev mode
+    is_dev: bool,
+    /// Gas limit targeted by the payload builders of the nodes
+    builder_gas_limit: Option<u64>,
+    /// Whether the nodes serve Prometheus metrics
+    metrics: bool,
+    /// Number of canonical blocks the nodes keep in memory before persisting them
+    persistence_threshold: Option<u64>,
+}
+
+/// Launches a single node on its own task manager.
+async fn launch_node<N>(
+    idx: usize,
+    chain_spec: Arc<N::ChainSpec>,
+    options: LaunchOptions,
+    storage: Option<NodeStorage>,
+) -> Result<(NodeHelperType<N>, TaskManager, NodeStorage)>
+where
+    N: NodeBuilderHelper,
+    LocalPayloadAttributesBuilder<N::ChainSpec>:
+        PayloadAttributesBuilder<<<N as NodeTypes>::Payload as PayloadTypes>::PayloadAttributes>,
+{
+    let tasks = TaskManager::current();
+    let exec = tasks.executor();
+
+    let network_config = NetworkArgs {
+        discovery: DiscoveryArgs { disable_discovery: true, ..DiscoveryArgs::default() },
+        ..NetworkArgs::default()
+    };
+
+    let is_restart = storage.is_some();
+    let datadir = storage.as_ref().map(|s| s.datadir.clone()).unwrap_or_else(tempdir_path);
+    let mut node_config = NodeConfig::new(chain_spec)
+        .with_network(network_config)
+        .with_unused_ports()
+        .with_rpc(
+            RpcServerArgs::default()
+                .with_unused_ports()
+                .with_http()
+                .with_http_api(RpcModuleSelection::All)
+                .with_ws(),
+        )
+        .with_datadir_args(DatadirArgs { datadir: datadir.clone().into(), ..Default::default() })
+        .with_payload_builder(PayloadBuilderArgs {
+            gas_limit: options.builder_gas_limit,
+            ..Default::default()
+        })
+        .set_dev(options.is_dev);
+    if options.metrics {
+        node_config = node_config.with_metrics(unused_local_addr()?);
+    }
+    let db = match storage {
+        Some(storage) => storage.db,
+        None => create_test_rw_db_with_path(node_config.datadir().db()),
+    };
+
+    let span = span!(Level::INFO, "node", idx);
+    let log_path = register_node_log(&span, datadir.join("logs").join("node.log"));
+    if log_path.is_none() {
+        debug!("Node {idx} has no log file");
+    }
+
+    let node = N::default();
+    let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config)
+        .with_database(db.clone())
+        .with_launch_context(exec)
+        .with_types_and_provider::<N, BlockchainProvider<_>>()
+        .with_components(node.components_builder())
+        .with_add_ons(node.add_ons())
+        .launch_with_fn(|builder| {
+            let mut tree_config = TreeConfig::default();
+            if let Some(persistence_threshold) = options.persistence_threshold {
+                tree_config = tree_config.with_persistence_threshold(persistence_threshold);
+            }
+            let launcher = EngineNodeLauncher::new(
+                builder.task_executor().clone(),
+                builder.config().datadir(),
+                tree_config,
+            );
+            builder.launch_with(launcher)
+        })
+        .instrument(span)
+        .await?;
+
+    let attributes_generator = move |timestamp| {
+        let attributes = PayloadAttributes {
+            timestamp,
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: alloy_primitives::Address::ZERO,
+            withdrawals: Some(vec![]),
+            parent_beacon_block_root: Some(B256::ZERO),
+        };
+        <<N as NodeTypes>::Payload as PayloadTypes>::PayloadBuilderAttributes::from(
+            EthPayloadBuilderAttributes::new(B256::ZERO, attributes),
+        )
+    };
+    let node = NodeTestContext::new(node, attributes_generator).await?;
+
+    // a relaunched node keeps the forkchoice it has persisted
+    if !is_restart {
+        let genesis = node.block_hash(0);
+        node.update_forkchoice(genesis, genesis).await?;
+    }
+
+    Ok((node, tasks, NodeStorage { db, datadir, log_path }))
+}
+
+/// Tracing layer that writes the events of each node launched by the testsuite to the node's own
+/// log file, `logs/node.log` in the node's datadir.
+///
+/// Per-node log files are opt-in: the test installs the layer as part of its own tracing
+/// subscriber, e.g. `tracing_subscriber::registry().with(NodeLogLayer::default())`, filtered to
+/// the desired level. Nodes launched while the layer is not part of the current subscriber have no
+/// log file.
+///
+/// Nodes are launched in a `node` span, which their tasks inherit, so an event is written to the
+/// log file registered for its closest enclosing node span.
+#[derive(Debug, Default)]
+pub struct NodeLogLayer {
+    /// Log files of the node spans that are still open
+    files: Mutex<HashMap<span::Id, Arc<Mutex<File>>>>,
+}
+
+impl<S> Layer<S> for NodeLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(mut scope) = ctx.event_scope(event) else { return };
+        let Some(file) = self
+            .files
+            .lock()
+            .ok()
+            .and_then(|files| scope.find_map(|span| files.get(&span.id()).cloned()))
+        else {
+            return
+        };
+
+        let metadata = event.metadata();
+        let mut line = format!("{} {}:", metadata.level(), metadata.target());
+        event.record(&mut LogLineVisitor(&mut line));
+        line.push('\n');
+        if let Ok(mut file) = file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn on_close(&self, id: span::Id, _ctx: Context<'_, S>) {
+        if let Ok(mut files) = self.files.lock() {
+            files.remove(&id);
+        }
+    }
+}
+
+/// Appends the fields of an event to a log line.
+struct LogLineVisitor<'a>(&'a mut String);
+
+impl Visit for LogLineVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = if field.name() == "message" {
+            write!(self.0, " {value:?}")
+        } else {
+            write!(self.0, " {}={value:?}", field.name())
+        };
+    }
+}
+
+/// Registers `path` as the log file of the node launched in `span` and returns it.
+///
+/// Returns `None` if the test did not install a [`NodeLogLayer`] as part of the current tracing
+/// subscriber, or if the log file could not be created.
+fn register_node_log(span: &Span, path: PathBuf) -> Option<PathBuf> {
+    span.with_subscriber(|(id, dispatch)| {
+        let layer = dispatch.downcast_ref::<NodeLogLayer>()?;
+        std::fs::create_dir_all(path.parent()?).ok()?;
+        let file = File::options().create(true).append(true).open(&path).ok()?;
+        layer.files.lock().ok()?.insert(id.clone(), Arc::new(Mutex::new(file)));
+        Some(path)
+    })
+    .flatten()
+}
+
+/// Returns a local address whose port is currently unused.
+fn unused_local_addr() -> Result<SocketAddr> {
+    Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?.local_addr()?)
+}
+
+/// Moves the node into its own task, which keeps it alive until it is shut down through the
+/// returned client's [`NodeLifecycle`].
+async fn spawn_node<N>(
+    node: NodeHelperType<N>,
+    tasks: TaskManager,
+    storage: NodeStorage,
+) -> Result<NodeClient>
+where
+    N: NodeBuilderHelper,
+{
+    let rpc =
+        node.rpc_client().ok_or_else(|| eyre!("Failed to create HTTP RPC client for node"))?;
+    let engine = node.engine_api_client();
+    let ws = node.ws_client().await.map(Arc::new);
+    let metrics = node.inner.config.metrics;
+    let log_path = storage.log_path.clone();
+    let peers = node.inner.network.peers_handle().clone();
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<(Duration, oneshot::Sender<bool>)>();
+    let panicked_tasks = Arc::new(Mutex::new(Vec::new()));
+    let node_panicked_tasks = panicked_tasks.clone();
+    tokio::spawn(async move {
+        // keep node and task manager in scope to ensure they're not dropped
+        let node = node;
+        let mut tasks = tasks;
+        let mut shutdown_rx = shutdown_rx;
+        // record panicking critical tasks until the shutdown signal is received
+        let shutdown = loop {
+            tokio::select! {
+                shutdown = &mut shutdown_rx => break shutdown,
+                err = &mut tasks => {
+                    error!(%err, "Node task panicked");
+                    if let Ok(mut panics) = node_panicked_tasks.lock() {
+                        panics.push(err.to_string());
                     }
                 }
             }
-            if retry_count == MAX_RETRIES {
-                return Err(eyre!("Failed to connect to node {idx} RPC endpoint after {MAX_RETRIES} retries: {:?}", last_error));
+        };
+        match shutdown {
+            Ok((timeout, exited_tx)) => {
+                drop(node);
+                let clean = tokio::task::spawn_blocking(move || {
+                    tasks.graceful_shutdown_with_timeout(timeout)
+                })
+                .await
+                .unwrap_or(false);
+                let _ = exited_tx.send(clean);
             }
+            // the lifecycle handle was dropped, node and tasks are dropped here
+            Err(_) => drop((node, tasks)),
         }
+    });
 
-        env.node_clients = node_clients;
+    Ok(NodeClient {
+        rpc,
+        ws,
+        engine,
+        metrics,
+        peers: Some(peers),
+        log_path,
+        lifecycle: Some(NodeLifecycle { storage, shutdown_tx: Some(shutdown_tx), panicked_tasks }),
+    })
+}
 
-        // TODO: For each block in self.blocks, replay it on the node
+/// Waits for the node to be ready to accept RPC requests.
+pub(crate) async fn wait_for_node_ready(idx: usize, client: &NodeClient) -> Result<()> {
+    let mut retry_count = 0;
+    const MAX_RETRIES: usize = 5;
+    let mut last_error = None;
 
-        Ok(())
+    while retry_count < MAX_RETRIES {
+        match EthApiClient::<Transaction, RpcBlock, Receipt, Header>::block_by_number(
+            &client.rpc,
+            BlockNumberOrTag::Latest,
+            false,
+        )
+        .await
+        {
+            Ok(_) => {
+                debug!("Node {idx} RPC endpoint is ready");
+                return Ok(())
+            }
+            Err(e) => {
+                last_error = Some(e);
+                retry_count += 1;
+                debug!("Node {idx} RPC endpoint not ready, retry {retry_count}/{MAX_RETRIES}");
+                sleep(Duration::from_millis(500)).await;
+            }
+        }
+    }
+
+    Err(eyre!(
+        "Failed to connect to node {idx} RPC endpoint after {MAX_RETRIES} retries: {:?}",
+        last_error
+    ))
+}
+
+/// Returns the node record of the node behind the given client.
+pub(crate) async fn node_record(client: &NodeClient) -> Result<NodeRecord> {
+    let info = AdminApiClient::node_info(&client.rpc).await?;
+    info.enode.parse().map_err(|e| eyre!("Invalid enode {}: {e}", info.enode))
+}
+
+/// Connects the two nodes through the admin API and waits until `node` reports a peer.
+pub(crate) async fn connect_nodes(
+    node: &NodeClient,
+    peer: &NodeClient,
+    timeout: Duration,
+) -> Result<()> {
+    let record = node_record(peer).await?;
+    AdminApiClient::add_peer(&node.rpc, record).await?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let peers = AdminApiClient::peers(&node.rpc).await?;
+        if peers.iter().any(|p| p.enode.parse::<NodeRecord>().is_ok_and(|r| r.id == record.id)) {
+            return Ok(())
+        }
+        if Instant::now() >= deadline {
+            return Err(eyre!("Peer {} did not connect within {timeout:?}", record.id))
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Disconnects `peer` from `node` through the admin API and waits until `node` no longer reports
+/// it as a peer.
+pub(crate) async fn disconnect_nodes(
+    node: &NodeClient,
+    peer: &NodeClient,
+    timeout: Duration,
+) -> Result<()> {
+    let record = node_record(peer).await?;
+    AdminApiClient::remove_peer(&node.rpc, record.into()).await?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let peers = AdminApiClient::peers(&node.rpc).await?;
+        if !peers.iter().any(|p| p.enode.parse::<NodeRecord>().is_ok_and(|r| r.id == record.id)) {
+            return Ok(())
+        }
+        if Instant::now() >= deadline {
+            return Err(eyre!("Peer {} did not disconnect within {timeout:?}", record.id))
+        }
+        sleep(Duration::from_millis(100)).await;
     }
 }
 
@@ -134,6 +134,10 @@ impl SignedTransaction for OpTransactionSigned {
         self.hash.get_or_init(|| self.recalculate_hash())
     }
 
+    fn is_gossipable(&self) -> bool {
+        !self.is_deposit()
+    }
+
     fn recover_signer_unchecked_with_buf(
         &self,
         buf: &mut Vec<u8>,
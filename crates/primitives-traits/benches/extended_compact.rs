@@ -0,0 +1,116 @@
+#![allow(missing_docs)]
+use alloy_consensus::{
+    constants::{EIP7702_TX_TYPE_ID, LEGACY_TX_TYPE_ID},
+    EthereumTxEnvelope, Signed, TxEip1559, TxEip2930, TxEip4844, TxEip7702, TxLegacy,
+};
+use alloy_eips::{eip2718::IsTyped2718, Typed2718};
+use alloy_primitives::{Signature, U256};
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_codecs::Compact;
+use reth_primitives_traits::ExtendedTxEnvelope;
+use std::hint::black_box;
+
+/// Builtin variant covering all Ethereum transaction types.
+type BuiltIn = EthereumTxEnvelope<TxEip4844>;
+
+/// Envelope of an Ethereum chain, all stored transactions are builtin.
+type Envelope = ExtendedTxEnvelope<BuiltIn, BuiltIn>;
+
+/// Returns a mix of all Ethereum transaction types, dominated by EIP-1559 and legacy
+/// transactions.
+fn transactions() -> Vec<BuiltIn> {
+    let signature = Signature::new(U256::from(1), U256::from(2), false);
+    (0..1000u64)
+        .map(|nonce| match nonce % 20 {
+            0 => BuiltIn::Eip2930(Signed::new_unhashed(
+                TxEip2930 { nonce, ..Default::default() },
+                signature,
+            )),
+            1 => BuiltIn::Eip4844(Signed::new_unhashed(
+                TxEip4844 { nonce, ..Default::default() },
+                signature,
+            )),
+            2 => BuiltIn::Eip7702(Signed::new_unhashed(
+                TxEip7702 { nonce, ..Default::default() },
+                signature,
+            )),
+            3..=6 => BuiltIn::Legacy(Signed::new_unhashed(
+                TxLegacy { nonce, ..Default::default() },
+                signature,
+            )),
+            _ => BuiltIn::Eip1559(Signed::new_unhashed(
+                TxEip1559 { nonce, ..Default::default() },
+                signature,
+            )),
+        })
+        .collect()
+}
+
+/// Compact encodings of the transactions and their lengths.
+fn encoded<T: Compact>(txs: impl IntoIterator<Item = T>) -> Vec<(Vec<u8>, usize)> {
+    txs.into_iter()
+        .map(|tx| {
+            let mut buf = Vec::new();
+            let len = tx.to_compact(&mut buf);
+            (buf, len)
+        })
+        .collect()
+}
+
+/// Builtin type check with a range fast path for types covering all Ethereum transaction types.
+fn is_built_in_type_range<B: IsTyped2718>(ty: u8) -> bool {
+    if ty <= EIP7702_TX_TYPE_ID && (LEGACY_TX_TYPE_ID..=EIP7702_TX_TYPE_ID).all(B::is_type) {
+        return true
+    }
+    B::is_type(ty)
+}
+
+/// Benchmarks bulk decoding of a realistic mix of Ethereum transactions, with decoding the bare
+/// builtin envelope as baseline, and the dispatch on the transaction type used by the decoding.
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let txs = transactions();
+    let bare = encoded(txs.iter().cloned());
+    let extended = encoded(txs.iter().cloned().map(Envelope::BuiltIn));
+    let types = txs.iter().map(Typed2718::ty).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("ExtendedTxEnvelope from_compact");
+    group.bench_function("baseline: EthereumTxEnvelope", |b| {
+        b.iter(|| {
+            for (buf, len) in &bare {
+                black_box(BuiltIn::from_compact(buf, *len));
+            }
+        })
+    });
+    group.bench_function("ExtendedTxEnvelope", |b| {
+        b.iter(|| {
+            for (buf, len) in &extended {
+                black_box(Envelope::from_compact(buf, *len));
+            }
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("ExtendedTxEnvelope builtin type dispatch");
+    group.bench_function("baseline: range fast path", |b| {
+        b.iter(|| {
+            for ty in &types {
+                black_box(is_built_in_type_range::<BuiltIn>(black_box(*ty)));
+            }
+        })
+    });
+    group.bench_function("is_type", |b| {
+        b.iter(|| {
+            for ty in &types {
+                black_box(BuiltIn::is_type(black_box(*ty)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -1,17 +1,23 @@
 use crate::{
+    error::GotExpected,
     size::InMemorySize,
     transaction::signed::{RecoveryError, SignedTransaction},
 };
 use alloc::vec::Vec;
-use alloy_consensus::{transaction::SignerRecoverable, Transaction};
+use alloy_consensus::{
+    transaction::{Recovered, SignerRecoverable},
+    Transaction,
+};
 use alloy_eips::{
     eip2718::{Eip2718Error, Eip2718Result, IsTyped2718},
     eip2930::AccessList,
+    eip4844::DATA_GAS_PER_BLOB,
     eip7702::SignedAuthorization,
     Decodable2718, Encodable2718, Typed2718,
 };
 use alloy_primitives::{ChainId, TxHash};
 use alloy_rlp::{BufMut, Decodable, Encodable, Result as RlpResult};
+use core::fmt;
 use revm_primitives::{Address, Bytes, TxKind, B256, U256};
 
 macro_rules! delegate {
@@ -29,7 +35,8 @@ macro_rules! delegate {
 /// transaction types.
 ///
 /// Note: The other transaction type variants must not overlap with the builtin one, transaction
-/// types must be unique.
+/// types must be unique. Use [`ExtendedTxEnvelope::other_checked`] to enforce this when custom
+/// transactions enter the system.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum ExtendedTxEnvelope<BuiltIn, Other> {
@@ -39,6 +46,55 @@ pub enum ExtendedTxEnvelope<BuiltIn, Other> {
     Other(Other),
 }
 
+impl<B, T> ExtendedTxEnvelope<B, T> {
+    /// Consumes the envelope and maps the builtin transaction with the given closure, leaving
+    /// [`ExtendedTxEnvelope::Other`] untouched.
+    ///
+    /// This is intended for migrating a collection of envelopes from one builtin preset to
+    /// another, e.g. when upgrading to a newer ethereum envelope type, while keeping the custom
+    /// transaction type as is.
+    pub fn into_built_in_mapped<B2>(self, f: impl FnOnce(B) -> B2) -> ExtendedTxEnvelope<B2, T> {
+        match self {
+            Self::BuiltIn(tx) => ExtendedTxEnvelope::BuiltIn(f(tx)),
+            Self::Other(tx) => ExtendedTxEnvelope::Other(tx),
+        }
+    }
+
+    /// Returns `true` if this is a [`ExtendedTxEnvelope::BuiltIn`] equal to `other`.
+    pub fn eq_built_in(&self, other: &B) -> bool
+    where
+        B: PartialEq,
+    {
+        matches!(self, Self::BuiltIn(tx) if tx == other)
+    }
+
+    /// Returns `true` if this is a [`ExtendedTxEnvelope::Other`] equal to `other`.
+    pub fn eq_other(&self, other: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        matches!(self, Self::Other(tx) if tx == other)
+    }
+}
+
+impl<B, T> ExtendedTxEnvelope<B, T>
+where
+    B: IsTyped2718,
+    T: Typed2718,
+{
+    /// Creates a new [`ExtendedTxEnvelope::Other`] after checking that the transaction type does
+    /// not collide with any of the builtin transaction types.
+    ///
+    /// Returns the colliding type id if `t.ty()` is also claimed by the builtin type.
+    pub fn other_checked(t: T) -> Result<Self, u8> {
+        let ty = t.ty();
+        if B::is_type(ty) {
+            return Err(ty)
+        }
+        Ok(Self::Other(t))
+    }
+}
+
 impl<B, T> Transaction for ExtendedTxEnvelope<B, T>
 where
     B: Transaction,
@@ -116,6 +172,143 @@ where
     }
 }
 
+impl<B, T> ExtendedTxEnvelope<B, T>
+where
+    B: Transaction,
+    T: Transaction,
+{
+    /// Computes the intrinsic gas of the transaction under the given fork rules.
+    ///
+    /// This accounts for the base cost, contract creation, calldata, access list and
+    /// authorization list of the transaction. The EIP-7623 calldata floor is not included.
+    pub fn intrinsic_gas(&self, rules: IntrinsicGasRules) -> u64 {
+        let mut gas = TX_BASE_GAS;
+
+        let input = self.input();
+        if self.is_create() {
+            gas += TX_CREATE_GAS;
+            if rules.eip3860 {
+                gas += INITCODE_WORD_GAS * (input.len() as u64).div_ceil(32);
+            }
+        }
+
+        let zero_bytes = input.iter().filter(|byte| **byte == 0).count() as u64;
+        let non_zero_bytes = input.len() as u64 - zero_bytes;
+        let non_zero_byte_gas =
+            if rules.eip2028 { TX_DATA_NON_ZERO_GAS_EIP2028 } else { TX_DATA_NON_ZERO_GAS };
+        gas += zero_bytes * TX_DATA_ZERO_GAS + non_zero_bytes * non_zero_byte_gas;
+
+        let (addresses, storage_keys) = self.access_list_size();
+        gas += addresses as u64 * ACCESS_LIST_ADDRESS_GAS +
+            storage_keys as u64 * ACCESS_LIST_STORAGE_KEY_GAS;
+
+        if let Some(authorization_list) = self.authorization_list() {
+            gas += authorization_list.len() as u64 * PER_EMPTY_ACCOUNT_GAS;
+        }
+
+        gas
+    }
+
+    /// Returns the total blob gas consumed by the transaction, or `0` if it carries no blobs.
+    pub fn blob_gas(&self) -> u64 {
+        delegate!(self => tx.blob_versioned_hashes())
+            .map_or(0, |hashes| hashes.len() as u64 * DATA_GAS_PER_BLOB)
+    }
+
+    /// Returns the maximum amount the transaction can cost the sender.
+    ///
+    /// This is `gas_limit * max_fee_per_gas + value`, plus `blob_gas * max_fee_per_blob_gas` for
+    /// blob transactions. The sender must hold at least this balance for the transaction to be
+    /// admitted to the pool.
+    pub fn max_cost(&self) -> U256 {
+        let mut cost =
+            U256::from(self.gas_limit()) * U256::from(self.max_fee_per_gas()) + self.value();
+        if let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas() {
+            cost += U256::from(self.blob_gas()) * U256::from(max_fee_per_blob_gas);
+        }
+        cost
+    }
+
+    /// Checks that the transaction carries the `expected` chain id.
+    ///
+    /// Transactions without a chain id, i.e. pre-EIP-155 legacy transactions, are rejected. Use
+    /// [`Self::check_chain_id`] where those are still allowed.
+    pub fn require_chain_id(&self, expected: ChainId) -> Result<(), ChainIdError> {
+        match self.chain_id() {
+            Some(chain_id) if chain_id == expected => Ok(()),
+            Some(chain_id) => Err(ChainIdError::Mismatch(GotExpected::new(chain_id, expected))),
+            None => Err(ChainIdError::Missing(expected)),
+        }
+    }
+
+    /// Checks that the transaction is valid on the chain with the `expected` chain id.
+    ///
+    /// Unlike [`Self::require_chain_id`], transactions without replay protection are accepted.
+    pub fn check_chain_id(&self, expected: ChainId) -> Result<(), ChainIdError> {
+        if self.chain_id().is_none() {
+            return Ok(())
+        }
+        self.require_chain_id(expected)
+    }
+
+    /// Returns the pool ordering key of the recovered transaction: the sender, the nonce and the
+    /// effective tip at the given base fee.
+    ///
+    /// The effective tip is `0` if the max fee does not cover the base fee.
+    pub fn recovered_ordering_key(tx: &Recovered<Self>, base_fee: u64) -> (Address, u64, u128) {
+        tx.inner().ordering_key_with_sender(tx.signer(), base_fee)
+    }
+
+    fn ordering_key_with_sender(&self, sender: Address, base_fee: u64) -> (Address, u64, u128) {
+        (sender, self.nonce(), self.effective_tip_per_gas(base_fee).unwrap_or_default())
+    }
+
+    /// Returns the number of addresses and the total number of storage keys in the access list.
+    ///
+    /// Returns `(0, 0)` if the transaction has no access list.
+    pub fn access_list_size(&self) -> (usize, usize) {
+        delegate!(self => tx.access_list()).map_or((0, 0), |access_list| {
+            let storage_keys = access_list.iter().map(|item| item.storage_keys.len()).sum();
+            (access_list.len(), storage_keys)
+        })
+    }
+}
+
+/// Fork dependent rules used by [`ExtendedTxEnvelope::intrinsic_gas`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntrinsicGasRules {
+    /// Whether the EIP-2028 calldata pricing is active (Istanbul).
+    pub eip2028: bool,
+    /// Whether the EIP-3860 initcode word cost is active (Shanghai).
+    pub eip3860: bool,
+}
+
+impl IntrinsicGasRules {
+    /// Rules of the latest fork.
+    pub const fn latest() -> Self {
+        Self { eip2028: true, eip3860: true }
+    }
+}
+
+/// Base gas of every transaction.
+const TX_BASE_GAS: u64 = 21_000;
+/// Additional gas of a contract creation transaction.
+const TX_CREATE_GAS: u64 = 32_000;
+/// Gas per zero byte of calldata.
+const TX_DATA_ZERO_GAS: u64 = 4;
+/// Gas per non-zero byte of calldata before EIP-2028.
+const TX_DATA_NON_ZERO_GAS: u64 = 68;
+/// Gas per non-zero byte of calldata since EIP-2028.
+const TX_DATA_NON_ZERO_GAS_EIP2028: u64 = 16;
+/// Gas per word of initcode, see EIP-3860.
+const INITCODE_WORD_GAS: u64 = 2;
+/// Gas per address in the access list, see EIP-2930.
+const ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+/// Gas per storage key in the access list, see EIP-2930.
+const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+/// Gas per authorization in the authorization list, see EIP-7702.
+const PER_EMPTY_ACCOUNT_GAS: u64 = 25_000;
+
 impl<B, T> IsTyped2718 for ExtendedTxEnvelope<B, T>
 where
     B: IsTyped2718,
@@ -162,6 +355,14 @@ where
         }
     }
 
+    fn is_gossipable(&self) -> bool {
+        delegate!(self => tx.is_gossipable())
+    }
+
+    fn is_broadcastable_in_full(&self) -> bool {
+        delegate!(self => tx.is_broadcastable_in_full())
+    }
+
     fn recover_signer_unchecked_with_buf(
         &self,
         buf: &mut Vec<u8>,
@@ -170,6 +371,148 @@ where
     }
 }
 
+impl<B, T> ExtendedTxEnvelope<B, T>
+where
+    B: SignedTransaction + IsTyped2718,
+    T: SignedTransaction,
+{
+    /// Returns a concise representation of the envelope that only shows the variant, the
+    /// transaction type and the transaction hash.
+    ///
+    /// This is intended for logging, where dumping the full inner transaction is too verbose.
+    pub const fn short(&self) -> ExtendedTxEnvelopeShort<'_, B, T> {
+        ExtendedTxEnvelopeShort(self)
+    }
+
+    /// Recovers the signer of the transaction and checks that it matches `expected`.
+    ///
+    /// On mismatch, the returned error has a [`GotExpected`] source with the recovered and the
+    /// expected address.
+    pub fn verify_signer(&self, expected: Address) -> Result<(), RecoveryError> {
+        let recovered = self.recover_signer()?;
+        if recovered != expected {
+            return Err(RecoveryError::from_source(GotExpected::new(recovered, expected)));
+        }
+        Ok(())
+    }
+
+    /// Returns the key used to prioritize the transaction in the pool: the sender, the nonce and
+    /// the effective tip at the given base fee.
+    ///
+    /// This recovers the sender, which is expensive. Callers that already hold a [`Recovered`]
+    /// transaction should use [`ExtendedTxEnvelope::recovered_ordering_key`] instead.
+    pub fn ordering_key(&self, base_fee: u64) -> Result<(Address, u64, u128), RecoveryError> {
+        Ok(self.ordering_key_with_sender(self.recover_signer()?, base_fee))
+    }
+
+    /// Returns whether this transaction may replace `other` in the pool.
+    ///
+    /// This requires the same sender and nonce, and fees bumped by at least `min_bump_percent`
+    /// over the fees of `other`: the max fee per gas, the max priority fee per gas if both
+    /// transactions set one, and the max fee per blob gas if `other` is a blob transaction.
+    pub fn is_valid_replacement_of(
+        &self,
+        other: &Self,
+        min_bump_percent: u64,
+    ) -> Result<bool, RecoveryError> {
+        if self.nonce() != other.nonce() || self.recover_signer()? != other.recover_signer()? {
+            return Ok(false)
+        }
+
+        let bumped =
+            |fee: u128| fee.saturating_add(fee.saturating_mul(min_bump_percent as u128) / 100);
+
+        if self.max_fee_per_gas() < bumped(other.max_fee_per_gas()) {
+            return Ok(false)
+        }
+
+        if let (Some(priority_fee), Some(other_priority_fee)) =
+            (self.max_priority_fee_per_gas(), other.max_priority_fee_per_gas())
+        {
+            if priority_fee < bumped(other_priority_fee) {
+                return Ok(false)
+            }
+        }
+
+        if let Some(other_blob_fee) = other.max_fee_per_blob_gas() {
+            if self.max_fee_per_blob_gas().unwrap_or_default() < bumped(other_blob_fee) {
+                return Ok(false)
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<B, T> ExtendedTxEnvelope<alloy_consensus::Signed<B>, alloy_consensus::Signed<T>>
+where
+    B: alloy_consensus::SignableTransaction<alloy_primitives::Signature> + Clone,
+    T: alloy_consensus::SignableTransaction<alloy_primitives::Signature> + Clone,
+{
+    /// Returns a copy of the transaction signed with the given secret key, keeping all other
+    /// fields.
+    ///
+    /// The transaction hash is recomputed for the new signature. This is intended for building
+    /// wrong-sender and signature malleability test cases.
+    ///
+    /// # Panics
+    ///
+    /// If `secret` is not a valid secp256k1 secret key.
+    pub fn resigned(&self, secret: B256) -> Self {
+        let sign = |signature_hash| {
+            crate::crypto::secp256k1::sign_message(secret, signature_hash)
+                .expect("invalid secret key")
+        };
+        match self {
+            Self::BuiltIn(tx) => {
+                let signature = sign(tx.signature_hash());
+                Self::BuiltIn(tx.tx().clone().into_signed(signature))
+            }
+            Self::Other(tx) => {
+                let signature = sign(tx.signature_hash());
+                Self::Other(tx.tx().clone().into_signed(signature))
+            }
+        }
+    }
+}
+
+/// Concise [`Debug`](core::fmt::Debug) and [`Display`](core::fmt::Display) representation of an
+/// [`ExtendedTxEnvelope`], see [`ExtendedTxEnvelope::short`].
+pub struct ExtendedTxEnvelopeShort<'a, B, T>(&'a ExtendedTxEnvelope<B, T>);
+
+impl<B, T> ExtendedTxEnvelopeShort<'_, B, T> {
+    const fn variant(&self) -> &'static str {
+        match self.0 {
+            ExtendedTxEnvelope::BuiltIn(_) => "BuiltIn",
+            ExtendedTxEnvelope::Other(_) => "Other",
+        }
+    }
+}
+
+impl<B, T> fmt::Debug for ExtendedTxEnvelopeShort<'_, B, T>
+where
+    B: SignedTransaction + IsTyped2718,
+    T: SignedTransaction,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(self.variant())
+            .field("ty", &self.0.ty())
+            .field("hash", self.0.tx_hash())
+            .finish()
+    }
+}
+
+impl<B, T> fmt::Display for ExtendedTxEnvelopeShort<'_, B, T>
+where
+    B: SignedTransaction + IsTyped2718,
+    T: SignedTransaction,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(ty: {}, hash: {})", self.variant(), self.0.ty(), self.0.tx_hash())
+    }
+}
+
 impl<B, T> Typed2718 for ExtendedTxEnvelope<B, T>
 where
     B: Typed2718,
@@ -183,6 +526,41 @@ where
     }
 }
 
+impl<B, T> ExtendedTxEnvelope<B, T>
+where
+    B: Decodable2718 + IsTyped2718,
+    T: Decodable2718,
+{
+    /// Decodes an EIP-2718 encoded transaction from a hex string, with or without `0x` prefix.
+    pub fn decode_2718_hex(s: &str) -> Result<Self, DecodeHexError> {
+        let bytes = alloy_primitives::hex::decode(s)?;
+        Ok(Self::decode_2718(&mut bytes.as_slice())?)
+    }
+}
+
+/// Error returned by [`ExtendedTxEnvelope::decode_2718_hex`].
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeHexError {
+    /// The input is not valid hex
+    #[error(transparent)]
+    Hex(#[from] alloy_primitives::hex::FromHexError),
+    /// The decoded bytes are not a valid EIP-2718 transaction
+    #[error(transparent)]
+    Eip2718(#[from] Eip2718Error),
+}
+
+/// Error returned by [`ExtendedTxEnvelope::require_chain_id`] and
+/// [`ExtendedTxEnvelope::check_chain_id`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ChainIdError {
+    /// The transaction has no chain id, the expected chain id is attached
+    #[error("transaction has no chain id, expected {0}")]
+    Missing(ChainId),
+    /// The transaction chain id does not match the expected one
+    #[error("transaction chain id mismatch: {0}")]
+    Mismatch(GotExpected<ChainId>),
+}
+
 impl<B, T> Decodable2718 for ExtendedTxEnvelope<B, T>
 where
     B: Decodable2718 + IsTyped2718,
@@ -225,6 +603,31 @@ where
     }
 }
 
+impl<B, T> ExtendedTxEnvelope<B, T>
+where
+    B: Encodable2718,
+    T: Encodable2718,
+{
+    /// Encodes the transactions as the transactions list of a block body.
+    ///
+    /// This writes an RLP list of the network encoded transactions: legacy transactions are
+    /// encoded as is, typed transactions are wrapped in an RLP string header.
+    pub fn encode_block_body_transactions(txs: &[Self], out: &mut dyn BufMut) {
+        let payload_length = txs.iter().map(Encodable2718::network_len).sum();
+        alloy_rlp::Header { list: true, payload_length }.encode(out);
+        for tx in txs {
+            tx.network_encode(out);
+        }
+    }
+
+    /// Returns the length of the transactions list encoded by
+    /// [`Self::encode_block_body_transactions`].
+    pub fn block_body_transactions_length(txs: &[Self]) -> usize {
+        let payload_length = txs.iter().map(Encodable2718::network_len).sum();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
 impl<B, T> Encodable for ExtendedTxEnvelope<B, T>
 where
     B: Encodable,
@@ -263,6 +666,89 @@ where
     }
 }
 
+mod ethereum {
+    use crate::ExtendedTxEnvelope;
+    use alloy_consensus::{
+        error::ValueError, EthereumTxEnvelope, Signed, TxEip4844, TxEip4844Variant, TxEnvelope,
+    };
+
+    impl<Tx> TryFrom<TxEnvelope> for ExtendedTxEnvelope<EthereumTxEnvelope<TxEip4844>, Tx> {
+        type Error = ValueError<TxEnvelope>;
+
+        /// Converts the transaction into the builtin variant.
+        ///
+        /// Blob transactions carrying a sidecar are rejected, since the sidecar would be lost.
+        fn try_from(tx: TxEnvelope) -> Result<Self, Self::Error> {
+            let tx = match tx {
+                TxEnvelope::Legacy(tx) => EthereumTxEnvelope::Legacy(tx),
+                TxEnvelope::Eip2930(tx) => EthereumTxEnvelope::Eip2930(tx),
+                TxEnvelope::Eip1559(tx) => EthereumTxEnvelope::Eip1559(tx),
+                TxEnvelope::Eip4844(tx) => {
+                    if matches!(tx.tx(), TxEip4844Variant::TxEip4844WithSidecar(_)) {
+                        return Err(ValueError::new(
+                            TxEnvelope::Eip4844(tx),
+                            "Cannot convert blob transaction with sidecar to EthereumTxEnvelope",
+                        ))
+                    }
+                    let (tx, signature, hash) = tx.into_parts();
+                    EthereumTxEnvelope::Eip4844(Signed::new_unchecked(tx.into(), signature, hash))
+                }
+                TxEnvelope::Eip7702(tx) => EthereumTxEnvelope::Eip7702(tx),
+            };
+            Ok(Self::BuiltIn(tx))
+        }
+    }
+}
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use crate::ExtendedTxEnvelope;
+    use alloy_consensus::{transaction::Recovered, Transaction};
+    use alloy_rpc_types_eth::TransactionInfo;
+    use revm_primitives::Address;
+
+    impl<B, T> ExtendedTxEnvelope<B, T>
+    where
+        B: Transaction,
+        T: Transaction,
+    {
+        /// Converts the transaction signed by `signer` into an RPC transaction response, filling
+        /// in the block context and effective gas price from `tx_info`.
+        ///
+        /// The returned type implements alloy's `TransactionResponse`, so it can be served by
+        /// the standard `eth` endpoints. In JSON, the block context fields and `from` keep their
+        /// standard names, while the transaction fields are nested under a `"BuiltIn"` or
+        /// `"Other"` key, following the serde representation of [`ExtendedTxEnvelope`].
+        pub fn into_rpc_transaction(
+            self,
+            signer: Address,
+            tx_info: TransactionInfo,
+        ) -> alloy_rpc_types_eth::Transaction<Self> {
+            let TransactionInfo {
+                block_hash,
+                block_number,
+                index: transaction_index,
+                base_fee,
+                ..
+            } = tx_info;
+
+            let effective_gas_price = base_fee
+                .map(|base_fee| {
+                    self.effective_tip_per_gas(base_fee).unwrap_or_default() + base_fee as u128
+                })
+                .unwrap_or_else(|| self.max_fee_per_gas());
+
+            alloy_rpc_types_eth::Transaction {
+                inner: Recovered::new_unchecked(self, signer),
+                block_hash,
+                block_number,
+                transaction_index,
+                effective_gas_price: Some(effective_gas_price),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "op")]
 mod op {
     use crate::ExtendedTxEnvelope;
@@ -350,6 +836,19 @@ mod serde_bincode_compat {
 #[cfg(feature = "reth-codec")]
 use alloy_primitives::bytes::Buf;
 
+/// Flag set on the first byte of a versioned [`Compact`](reth_codecs::Compact) encoding of an
+/// [`ExtendedTxEnvelope`].
+///
+/// Transaction types are always below `0x80`, so data written before the header was versioned,
+/// which starts with the bare transaction type, is read as version 0.
+#[cfg(feature = "reth-codec")]
+const COMPACT_VERSION_FLAG: u8 = 0x80;
+
+/// Current version of the [`Compact`](reth_codecs::Compact) encoding of an
+/// [`ExtendedTxEnvelope`].
+#[cfg(feature = "reth-codec")]
+const COMPACT_VERSION: u8 = 1;
+
 #[cfg(feature = "reth-codec")]
 impl<B, T> reth_codecs::Compact for ExtendedTxEnvelope<B, T>
 where
@@ -360,6 +859,7 @@ where
     where
         Buf: alloy_rlp::bytes::BufMut + AsMut<[u8]>,
     {
+        buf.put_u8(COMPACT_VERSION_FLAG | COMPACT_VERSION);
         buf.put_u8(self.ty());
         match self {
             Self::BuiltIn(tx) => tx.to_compact(buf),
@@ -368,9 +868,21 @@ where
     }
 
     fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
-        let type_byte = buf.get_u8();
+        let header = buf.get_u8();
+        let type_byte = if header & COMPACT_VERSION_FLAG == 0 {
+            // version 0, the header is the transaction type
+            header
+        } else {
+            match header & !COMPACT_VERSION_FLAG {
+                1 => buf.get_u8(),
+                version => panic!(
+                    "unsupported ExtendedTxEnvelope compact version {version}, expected at most \
+                     {COMPACT_VERSION}"
+                ),
+            }
+        };
 
-        if <B as IsTyped2718>::is_type(type_byte) {
+        if B::is_type(type_byte) {
             let (tx, remaining) = B::from_compact(buf, len);
             return (Self::BuiltIn(tx), remaining);
         }
@@ -379,3 +891,607 @@ where
         (Self::Other(tx), remaining)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{
+        SignableTransaction, Signed, TxEip1559, TxEip2930, TxEip4844, TxEnvelope, TxLegacy,
+        TypedTransaction,
+    };
+    use alloy_eips::eip2930::AccessListItem;
+    use alloy_primitives::Signature;
+
+    #[test]
+    fn into_built_in_mapped_migrates_collection() {
+        let legacy = TxLegacy { nonce: 1, ..Default::default() };
+        let other = TxEip1559 { nonce: 2, ..Default::default() };
+        let envelopes: Vec<ExtendedTxEnvelope<TxLegacy, TxEip1559>> = vec![
+            ExtendedTxEnvelope::BuiltIn(legacy.clone()),
+            ExtendedTxEnvelope::Other(other.clone()),
+        ];
+
+        let migrated: Vec<ExtendedTxEnvelope<TypedTransaction, TxEip1559>> = envelopes
+            .into_iter()
+            .map(|tx| tx.into_built_in_mapped(TypedTransaction::Legacy))
+            .collect();
+
+        assert_eq!(
+            migrated,
+            vec![
+                ExtendedTxEnvelope::BuiltIn(TypedTransaction::Legacy(legacy)),
+                ExtendedTxEnvelope::Other(other),
+            ]
+        );
+    }
+
+    #[test]
+    fn eq_inner() {
+        let legacy = TxLegacy { nonce: 1, ..Default::default() };
+        let other = TxEip1559 { nonce: 1, ..Default::default() };
+        let envelope = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(legacy.clone());
+
+        assert!(envelope.eq_built_in(&legacy));
+        assert!(!envelope.eq_built_in(&TxLegacy::default()));
+        assert!(!envelope.eq_other(&other));
+        assert!(ExtendedTxEnvelope::<TxLegacy, TxEip1559>::Other(other.clone()).eq_other(&other));
+    }
+
+    #[test]
+    fn other_checked_rejects_builtin_type() {
+        let tx = TxEip1559::default();
+
+        let res = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::other_checked(tx.clone());
+        assert_eq!(res, Ok(ExtendedTxEnvelope::Other(tx.clone())));
+
+        let res = ExtendedTxEnvelope::<TxEip1559, TxEip1559>::other_checked(tx);
+        assert_eq!(res, Err(2));
+    }
+
+    #[test]
+    fn intrinsic_gas() {
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(TxLegacy {
+            to: TxKind::Call(Address::ZERO),
+            input: Bytes::from_static(&[0, 1, 2]),
+            ..Default::default()
+        });
+        assert_eq!(tx.intrinsic_gas(IntrinsicGasRules::latest()), 21_000 + 4 + 2 * 16);
+        assert_eq!(tx.intrinsic_gas(IntrinsicGasRules::default()), 21_000 + 4 + 2 * 68);
+
+        let create = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(TxLegacy {
+            to: TxKind::Create,
+            input: Bytes::from_static(&[1; 33]),
+            ..Default::default()
+        });
+        assert_eq!(
+            create.intrinsic_gas(IntrinsicGasRules::latest()),
+            21_000 + 32_000 + 2 * 2 + 33 * 16
+        );
+    }
+
+    #[test]
+    fn blob_gas() {
+        let blob = TxEip4844 {
+            blob_versioned_hashes: vec![B256::ZERO, B256::with_last_byte(1)],
+            ..Default::default()
+        };
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip4844>::Other(blob);
+        assert_eq!(tx.blob_gas(), 2 * DATA_GAS_PER_BLOB);
+
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip4844>::BuiltIn(TxLegacy::default());
+        assert_eq!(tx.blob_gas(), 0);
+    }
+
+    #[test]
+    fn access_list_size() {
+        let access_list = AccessList(vec![
+            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO; 2] },
+            AccessListItem { address: Address::with_last_byte(1), storage_keys: vec![B256::ZERO] },
+        ]);
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::Other(TxEip1559 {
+            access_list,
+            ..Default::default()
+        });
+        assert_eq!(tx.access_list_size(), (2, 3));
+
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(TxLegacy::default());
+        assert_eq!(tx.access_list_size(), (0, 0));
+    }
+
+    #[test]
+    fn try_from_tx_envelope_rejects_blob_sidecar() {
+        use alloy_consensus::{EthereumTxEnvelope, TxEip4844Variant, TxEip4844WithSidecar};
+        use alloy_eips::eip4844::BlobTransactionSidecar;
+
+        type Envelope = ExtendedTxEnvelope<EthereumTxEnvelope<TxEip4844>, TxLegacy>;
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let tx = TxEip4844 { nonce: 1, ..Default::default() };
+
+        let envelope = TxEnvelope::Eip4844(Signed::new_unhashed(
+            TxEip4844Variant::TxEip4844(tx.clone()),
+            signature,
+        ));
+        let Ok(ExtendedTxEnvelope::BuiltIn(EthereumTxEnvelope::Eip4844(converted))) =
+            Envelope::try_from(envelope)
+        else {
+            panic!("blob transaction without sidecar not converted")
+        };
+        assert_eq!(converted.tx(), &tx);
+
+        let envelope = TxEnvelope::Eip4844(Signed::new_unhashed(
+            TxEip4844Variant::TxEip4844WithSidecar(TxEip4844WithSidecar::from_tx_and_sidecar(
+                tx,
+                BlobTransactionSidecar::default(),
+            )),
+            signature,
+        ));
+        assert!(Envelope::try_from(envelope).is_err());
+    }
+
+    #[test]
+    fn max_cost() {
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip4844>::Other(TxEip4844 {
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            value: U256::from(5),
+            max_fee_per_blob_gas: 3,
+            blob_versioned_hashes: vec![B256::ZERO; 2],
+            ..Default::default()
+        });
+        assert_eq!(tx.max_cost(), U256::from(21_000 * 10 + 5 + 2 * DATA_GAS_PER_BLOB * 3));
+
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip4844>::BuiltIn(TxLegacy {
+            gas_limit: 21_000,
+            gas_price: 7,
+            value: U256::from(1),
+            ..Default::default()
+        });
+        assert_eq!(tx.max_cost(), U256::from(21_000u64 * 7 + 1));
+    }
+
+    #[test]
+    fn require_chain_id() {
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::Other(TxEip1559 {
+            chain_id: 1,
+            ..Default::default()
+        });
+        assert_eq!(tx.require_chain_id(1), Ok(()));
+        assert_eq!(tx.check_chain_id(1), Ok(()));
+        assert_eq!(tx.require_chain_id(2), Err(ChainIdError::Mismatch(GotExpected::new(1, 2))));
+        assert_eq!(tx.check_chain_id(2), Err(ChainIdError::Mismatch(GotExpected::new(1, 2))));
+
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(TxLegacy::default());
+        assert_eq!(tx.require_chain_id(1), Err(ChainIdError::Missing(1)));
+        assert_eq!(tx.check_chain_id(1), Ok(()));
+    }
+
+    #[test]
+    fn recovered_ordering_key() {
+        let sender = Address::with_last_byte(1);
+        let tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::Other(TxEip1559 {
+            nonce: 3,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 2,
+            ..Default::default()
+        });
+        let recovered = Recovered::new_unchecked(tx, sender);
+
+        assert_eq!(ExtendedTxEnvelope::recovered_ordering_key(&recovered, 5), (sender, 3, 2));
+        assert_eq!(ExtendedTxEnvelope::recovered_ordering_key(&recovered, 9), (sender, 3, 1));
+        assert_eq!(ExtendedTxEnvelope::recovered_ordering_key(&recovered, 11), (sender, 3, 0));
+    }
+
+    #[test]
+    fn is_valid_replacement_of() {
+        let signed = |nonce, max_fee_per_gas, max_priority_fee_per_gas, secret| {
+            let tx = TxEip1559 {
+                nonce,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                ..Default::default()
+            };
+            let signature = crate::crypto::secp256k1::sign_message(
+                B256::with_last_byte(secret),
+                tx.signature_hash(),
+            )
+            .unwrap();
+            ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::Other(TxEnvelope::Eip1559(
+                tx.into_signed(signature),
+            ))
+        };
+        let existing = signed(1, 100, 10, 1);
+
+        assert!(signed(1, 110, 11, 1).is_valid_replacement_of(&existing, 10).unwrap());
+        // fee bumps below the threshold
+        assert!(!signed(1, 109, 11, 1).is_valid_replacement_of(&existing, 10).unwrap());
+        assert!(!signed(1, 110, 10, 1).is_valid_replacement_of(&existing, 10).unwrap());
+        assert!(!signed(1, 110, 0, 1).is_valid_replacement_of(&existing, 10).unwrap());
+        // bumping a fee at the top of the range must not overflow
+        let max_fee = signed(1, u128::MAX, 10, 1);
+        assert!(signed(1, u128::MAX, 11, 1).is_valid_replacement_of(&max_fee, 10).unwrap());
+        assert!(!signed(1, u128::MAX / 2, 11, 1).is_valid_replacement_of(&max_fee, 10).unwrap());
+        // different nonce or sender
+        assert!(!signed(2, 200, 20, 1).is_valid_replacement_of(&existing, 10).unwrap());
+        assert!(!signed(1, 200, 20, 2).is_valid_replacement_of(&existing, 10).unwrap());
+    }
+
+    #[test]
+    fn resigned() {
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let envelope = ExtendedTxEnvelope::<Signed<TxLegacy>, Signed<TxEip1559>>::Other(
+            Signed::new_unhashed(TxEip1559 { nonce: 7, ..Default::default() }, signature),
+        );
+
+        let ExtendedTxEnvelope::Other(original) = &envelope else { unreachable!() };
+        let resigned = envelope.resigned(B256::with_last_byte(1));
+        let ExtendedTxEnvelope::Other(tx) = &resigned else { panic!("variant changed") };
+        assert_eq!(tx.tx(), original.tx());
+        assert_ne!(tx.signature(), &signature);
+        assert_ne!(tx.hash(), original.hash());
+        // address of the secret key `0x01`
+        assert_eq!(
+            tx.recover_signer().unwrap(),
+            alloy_primitives::address!("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf")
+        );
+    }
+
+    #[test]
+    fn verify_signer() {
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let envelope = ExtendedTxEnvelope::<Signed<TxLegacy>, Signed<TxEip1559>>::Other(
+            Signed::new_unhashed(TxEip1559::default(), signature),
+        )
+        .resigned(B256::with_last_byte(1));
+        // address of the secret key `0x01`
+        let signer = alloy_primitives::address!("0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+        assert!(envelope.verify_signer(signer).is_ok());
+
+        let expected = Address::with_last_byte(1);
+        let err = envelope.verify_signer(expected).unwrap_err();
+        let source = core::error::Error::source(&err)
+            .and_then(|source| source.downcast_ref::<GotExpected<Address>>())
+            .expect("mismatch error has a GotExpected source");
+        assert_eq!(source, &GotExpected::new(signer, expected));
+    }
+
+    #[test]
+    fn short() {
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let legacy_hash = B256::with_last_byte(1);
+        let eip1559_hash = B256::with_last_byte(2);
+        let legacy = ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::BuiltIn(TxEnvelope::Legacy(
+            Signed::new_unchecked(TxLegacy::default(), signature, legacy_hash),
+        ));
+        let eip1559 = ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::Other(TxEnvelope::Eip1559(
+            Signed::new_unchecked(TxEip1559::default(), signature, eip1559_hash),
+        ));
+
+        assert_eq!(legacy.short().to_string(), format!("BuiltIn(ty: 0, hash: {legacy_hash})"));
+        assert_eq!(eip1559.short().to_string(), format!("Other(ty: 2, hash: {eip1559_hash})"));
+        assert_eq!(
+            format!("{:?}", legacy.short()),
+            format!("BuiltIn {{ ty: 0, hash: {legacy_hash:?} }}")
+        );
+        assert_eq!(
+            format!("{:?}", eip1559.short()),
+            format!("Other {{ ty: 2, hash: {eip1559_hash:?} }}")
+        );
+    }
+
+    #[test]
+    fn decode_2718_hex() {
+        type Envelope = ExtendedTxEnvelope<TxEnvelope, TxEnvelope>;
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let envelope = Envelope::BuiltIn(TxEnvelope::Eip1559(
+            TxEip1559 { chain_id: 1, nonce: 1, ..Default::default() }.into_signed(signature),
+        ));
+        let encoded = envelope.encoded_2718();
+
+        let prefixed = alloy_primitives::hex::encode_prefixed(&encoded);
+        assert_eq!(Envelope::decode_2718_hex(&prefixed).unwrap(), envelope);
+        let unprefixed = alloy_primitives::hex::encode(&encoded);
+        assert_eq!(Envelope::decode_2718_hex(&unprefixed).unwrap(), envelope);
+
+        assert!(matches!(Envelope::decode_2718_hex("0xzz"), Err(DecodeHexError::Hex(_))));
+        // valid hex, but an EIP-2930 type byte without a transaction
+        assert!(matches!(Envelope::decode_2718_hex("0x01"), Err(DecodeHexError::Eip2718(_))));
+    }
+
+    #[test]
+    fn is_gossipable() {
+        use alloy_consensus::TxEip4844Variant;
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let eip1559 = TxEnvelope::Eip1559(TxEip1559::default().into_signed(signature));
+        let eip4844 = TxEnvelope::Eip4844(
+            TxEip4844Variant::TxEip4844(TxEip4844::default()).into_signed(signature),
+        );
+
+        for tx in [
+            ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::BuiltIn(eip1559.clone()),
+            ExtendedTxEnvelope::Other(eip1559),
+        ] {
+            assert!(tx.is_gossipable());
+            assert!(tx.is_broadcastable_in_full());
+        }
+
+        // blob transactions are gossiped, but only announced by hash
+        for tx in [
+            ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::BuiltIn(eip4844.clone()),
+            ExtendedTxEnvelope::Other(eip4844),
+        ] {
+            assert!(tx.is_gossipable());
+            assert!(!tx.is_broadcastable_in_full());
+        }
+    }
+
+    #[cfg(feature = "op")]
+    #[test]
+    fn deposit_is_not_gossipable() {
+        use alloy_consensus::Sealed;
+        use op_alloy_consensus::{OpTxEnvelope, TxDeposit};
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let eip1559 = OpTxEnvelope::Eip1559(TxEip1559::default().into_signed(signature));
+        let deposit =
+            OpTxEnvelope::Deposit(Sealed::new_unchecked(TxDeposit::default(), B256::ZERO));
+
+        let tx = ExtendedTxEnvelope::<TxEnvelope, OpTxEnvelope>::Other(eip1559);
+        assert!(tx.is_gossipable());
+        assert!(tx.is_broadcastable_in_full());
+
+        let tx = ExtendedTxEnvelope::<TxEnvelope, OpTxEnvelope>::Other(deposit);
+        assert!(!tx.is_gossipable());
+        assert!(!tx.is_broadcastable_in_full());
+    }
+
+    #[test]
+    fn encode_block_body_transactions() {
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let legacy = TxEnvelope::Legacy(Signed::new_unchecked(
+            TxLegacy::default(),
+            signature,
+            B256::with_last_byte(1),
+        ));
+        let eip1559 = TxEnvelope::Eip1559(Signed::new_unchecked(
+            TxEip1559::default(),
+            signature,
+            B256::with_last_byte(2),
+        ));
+
+        let txs = vec![
+            ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::BuiltIn(legacy.clone()),
+            ExtendedTxEnvelope::<TxEnvelope, TxEnvelope>::Other(eip1559.clone()),
+        ];
+
+        let mut encoded = Vec::new();
+        ExtendedTxEnvelope::encode_block_body_transactions(&txs, &mut encoded);
+
+        assert_eq!(encoded, alloy_rlp::encode(vec![legacy, eip1559]));
+        assert_eq!(encoded.len(), ExtendedTxEnvelope::block_body_transactions_length(&txs));
+    }
+
+    #[cfg(feature = "rpc")]
+    #[test]
+    fn into_rpc_transaction() {
+        use alloy_rpc_types_eth::TransactionInfo;
+
+        let signer = Address::with_last_byte(1);
+        let other =
+            TxEip1559 { max_fee_per_gas: 10, max_priority_fee_per_gas: 2, ..Default::default() };
+        let tx_info = TransactionInfo {
+            block_hash: Some(B256::with_last_byte(2)),
+            block_number: Some(3),
+            index: Some(4),
+            base_fee: Some(5),
+            ..Default::default()
+        };
+
+        let rpc_tx = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::Other(other.clone())
+            .into_rpc_transaction(signer, tx_info);
+
+        assert_eq!(rpc_tx.inner.signer(), signer);
+        assert!(rpc_tx.inner.eq_other(&other));
+        assert_eq!(rpc_tx.block_hash, tx_info.block_hash);
+        assert_eq!(rpc_tx.block_number, Some(3));
+        assert_eq!(rpc_tx.transaction_index, Some(4));
+        assert_eq!(rpc_tx.effective_gas_price, Some(7));
+
+        let json = serde_json::to_value(&rpc_tx).unwrap();
+        assert_eq!(json["from"], serde_json::json!(signer));
+        assert!(json.get("Other").is_some());
+    }
+
+    #[cfg(feature = "reth-codec")]
+    #[test]
+    fn compact_versioned_header_roundtrip() {
+        use reth_codecs::Compact;
+
+        let envelopes: [ExtendedTxEnvelope<TxLegacy, TxEip1559>; 2] = [
+            ExtendedTxEnvelope::BuiltIn(TxLegacy { nonce: 1, ..Default::default() }),
+            ExtendedTxEnvelope::Other(TxEip1559 { nonce: 2, ..Default::default() }),
+        ];
+
+        for envelope in envelopes {
+            let mut buf = Vec::new();
+            let len = envelope.to_compact(&mut buf);
+            assert_eq!(buf[0], COMPACT_VERSION_FLAG | COMPACT_VERSION);
+            assert_eq!(buf[1], envelope.ty());
+
+            let (decoded, _) = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::from_compact(&buf, len);
+            assert_eq!(decoded, envelope);
+
+            // unversioned data is read as version 0
+            let (decoded, _) =
+                ExtendedTxEnvelope::<TxLegacy, TxEip1559>::from_compact(&buf[1..], len);
+            assert_eq!(decoded, envelope);
+        }
+    }
+
+    #[cfg(feature = "reth-codec")]
+    #[test]
+    #[should_panic(expected = "unsupported ExtendedTxEnvelope compact version 127")]
+    fn compact_unknown_version_panics() {
+        use reth_codecs::Compact;
+
+        let envelope = ExtendedTxEnvelope::<TxLegacy, TxEip1559>::BuiltIn(TxLegacy::default());
+        let mut buf = Vec::new();
+        let len = envelope.to_compact(&mut buf);
+        buf[0] = COMPACT_VERSION_FLAG | 0x7F;
+
+        ExtendedTxEnvelope::<TxLegacy, TxEip1559>::from_compact(&buf, len);
+    }
+
+    /// Unsigned transactions, covering the RLP and compact encodings.
+    fn unsigned_samples() -> [ExtendedTxEnvelope<TxEip1559, TxEip2930>; 2] {
+        [
+            ExtendedTxEnvelope::BuiltIn(TxEip1559 {
+                chain_id: 1,
+                nonce: 1,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(1)),
+                ..Default::default()
+            }),
+            ExtendedTxEnvelope::Other(TxEip2930 {
+                chain_id: 1,
+                nonce: 2,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::with_last_byte(2)),
+                ..Default::default()
+            }),
+        ]
+    }
+
+    /// Built-in transaction preset accepting EIP-1559 transactions only.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct BuiltIn(Signed<TxEip1559>);
+
+    impl Typed2718 for BuiltIn {
+        fn ty(&self) -> u8 {
+            self.0.ty()
+        }
+    }
+
+    impl IsTyped2718 for BuiltIn {
+        fn is_type(type_id: u8) -> bool {
+            TxEip1559::is_type(type_id)
+        }
+    }
+
+    impl Encodable2718 for BuiltIn {
+        fn encode_2718_len(&self) -> usize {
+            self.0.encode_2718_len()
+        }
+
+        fn encode_2718(&self, out: &mut dyn BufMut) {
+            self.0.encode_2718(out)
+        }
+    }
+
+    impl Decodable2718 for BuiltIn {
+        fn typed_decode(ty: u8, buf: &mut &[u8]) -> Eip2718Result<Self> {
+            Signed::<TxEip1559>::typed_decode(ty, buf).map(Self)
+        }
+
+        fn fallback_decode(buf: &mut &[u8]) -> Eip2718Result<Self> {
+            Signed::<TxEip1559>::fallback_decode(buf).map(Self)
+        }
+    }
+
+    /// Signed transactions, covering the EIP-2718 encoding.
+    fn signed_samples() -> [ExtendedTxEnvelope<BuiltIn, Signed<TxEip2930>>; 2] {
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let [ExtendedTxEnvelope::BuiltIn(built_in), ExtendedTxEnvelope::Other(other)] =
+            unsigned_samples()
+        else {
+            unreachable!()
+        };
+        [
+            ExtendedTxEnvelope::BuiltIn(BuiltIn(Signed::new_unhashed(built_in, signature))),
+            ExtendedTxEnvelope::Other(Signed::new_unhashed(other, signature)),
+        ]
+    }
+
+    /// Asserts the type of the transaction dispatches to its variant when decoding: `ty()` of a
+    /// builtin transaction must be recognized by the builtin `is_type`, and must not be for other
+    /// transactions.
+    fn assert_ty_dispatches_to_variant<B, T>(tx: &ExtendedTxEnvelope<B, T>)
+    where
+        B: Typed2718 + IsTyped2718,
+        T: Typed2718,
+    {
+        match tx {
+            ExtendedTxEnvelope::BuiltIn(_) => assert!(B::is_type(tx.ty()), "{}", tx.ty()),
+            ExtendedTxEnvelope::Other(_) => assert!(!B::is_type(tx.ty()), "{}", tx.ty()),
+        }
+    }
+
+    #[test]
+    fn ty_is_recognized_by_is_type() {
+        for tx in unsigned_samples() {
+            assert!(ExtendedTxEnvelope::<TxEip1559, TxEip2930>::is_type(tx.ty()));
+            assert_ty_dispatches_to_variant(&tx);
+        }
+        for tx in signed_samples() {
+            assert_ty_dispatches_to_variant(&tx);
+        }
+    }
+
+    #[test]
+    fn rlp_roundtrip() {
+        for envelope in unsigned_samples() {
+            let encoded = alloy_rlp::encode(&envelope);
+            let decoded: ExtendedTxEnvelope<TxEip1559, TxEip2930> =
+                Decodable::decode(&mut encoded.as_slice()).unwrap();
+            assert_eq!(decoded, envelope);
+        }
+    }
+
+    #[test]
+    fn eip2718_roundtrip() {
+        for envelope in signed_samples() {
+            let encoded = envelope.encoded_2718();
+            let decoded: ExtendedTxEnvelope<BuiltIn, Signed<TxEip2930>> =
+                Decodable2718::decode_2718(&mut encoded.as_slice()).unwrap();
+            assert_eq!(decoded, envelope);
+        }
+    }
+
+    #[cfg(feature = "reth-codec")]
+    #[test]
+    fn compact_roundtrip() {
+        use reth_codecs::Compact;
+
+        for envelope in unsigned_samples() {
+            let mut buf = Vec::new();
+            let len = envelope.to_compact(&mut buf);
+            let (decoded, _) = ExtendedTxEnvelope::<TxEip1559, TxEip2930>::from_compact(&buf, len);
+            assert_eq!(decoded, envelope);
+        }
+    }
+
+    #[cfg(feature = "serde-bincode-compat")]
+    #[test]
+    fn bincode_compat_roundtrip() {
+        use crate::serde_bincode_compat::SerdeBincodeCompat;
+        use alloy_consensus::{EthereumTxEnvelope, TxEip4844};
+
+        type Envelope =
+            ExtendedTxEnvelope<EthereumTxEnvelope<TxEip4844>, EthereumTxEnvelope<TxEip4844>>;
+
+        for envelope in signed_samples() {
+            let envelope: Envelope = match envelope {
+                ExtendedTxEnvelope::BuiltIn(tx) => ExtendedTxEnvelope::BuiltIn(tx.0.into()),
+                ExtendedTxEnvelope::Other(tx) => ExtendedTxEnvelope::Other(tx.into()),
+            };
+
+            let encoded = bincode::serialize(&envelope.as_repr()).unwrap();
+            let repr: <Envelope as SerdeBincodeCompat>::BincodeRepr<'_> =
+                bincode::deserialize(&encoded).unwrap();
+            assert_eq!(Envelope::from_repr(repr), envelope);
+        }
+    }
+}
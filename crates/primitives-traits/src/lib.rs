@@ -11,6 +11,8 @@
 //! - `arbitrary`: Adds `proptest` and `arbitrary` support for primitive types.
 //! - `op`: Implements the traits for various [op-alloy](https://github.com/alloy-rs/op-alloy)
 //!   types.
+//! - `rpc`: Adds conversions from the extended transaction envelope into alloy's RPC transaction
+//!   response.
 //! - `reth-codec`: Enables db codec support for reth types including zstd compression for certain
 //!   types.
 //! - `serde`: Adds serde support for all types.
@@ -123,7 +125,9 @@ pub use storage::StorageEntry;
 pub mod sync;
 
 mod extended;
-pub use extended::ExtendedTxEnvelope;
+pub use extended::{
+    ChainIdError, DecodeHexError, ExtendedTxEnvelope, ExtendedTxEnvelopeShort, IntrinsicGasRules,
+};
 /// Common header types
 pub mod header;
 pub use header::{Header, HeaderError, SealedHeader, SealedHeaderFor};
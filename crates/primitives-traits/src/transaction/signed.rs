@@ -43,14 +43,24 @@ pub trait SignedTransaction:
     /// Returns reference to transaction hash.
     fn tx_hash(&self) -> &TxHash;
 
+    /// Returns whether this transaction may be gossiped to peers at all.
+    ///
+    /// Some transactions are never propagated over the network, e.g. optimism deposit
+    /// transactions, which are derived from L1. Gossipable transactions are not necessarily
+    /// broadcastable in full, see [`SignedTransaction::is_broadcastable_in_full`].
+    fn is_gossipable(&self) -> bool {
+        true
+    }
+
     /// Returns whether this transaction type can be __broadcasted__ as full transaction over the
     /// network.
     ///
     /// Some transactions are not broadcastable as objects and only allowed to be broadcasted as
-    /// hashes, e.g. because they missing context (e.g. blob sidecar).
+    /// hashes, e.g. because they missing context (e.g. blob sidecar). Transactions that are not
+    /// [gossipable](SignedTransaction::is_gossipable) are never broadcastable in full.
     fn is_broadcastable_in_full(&self) -> bool {
         // EIP-4844 transactions are not broadcastable in full, only hashes are allowed.
-        !self.is_eip4844()
+        self.is_gossipable() && !self.is_eip4844()
     }
 
     /// Recover signer from signature and hash.
@@ -190,6 +200,10 @@ mod op {
             }
         }
 
+        fn is_gossipable(&self) -> bool {
+            !matches!(self, Self::Deposit(_))
+        }
+
         fn recover_signer_unchecked_with_buf(
             &self,
             buf: &mut Vec<u8>,